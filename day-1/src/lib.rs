@@ -0,0 +1,4 @@
+// Library interface for safe dial rotation
+
+pub mod parser;
+pub mod simulator;
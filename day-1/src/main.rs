@@ -1,13 +1,10 @@
-mod parser;
-mod simulator;
-
 use std::env;
 use std::fs;
 use std::io;
 use std::process;
 
-use parser::parse_rotations;
-use simulator::{count_all_zero_passes, count_zero_crossings};
+use safe_dial_rotation::parser::parse_rotations_with_max;
+use safe_dial_rotation::simulator::{count_all_zero_passes, count_zero_crossings};
 
 /// Counting method selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +13,8 @@ enum CountingMethod {
     EndOfRotation,
     /// Method 0x434C49434B: count all passes through 0 during rotations
     AllPasses,
+    /// Report both counts side by side
+    Both,
 }
 
 /// Read the contents of the input file
@@ -46,6 +45,7 @@ fn parse_counting_method() -> CountingMethod {
             match args[i + 1].as_str() {
                 "original" | "end" => return CountingMethod::EndOfRotation,
                 "all" | "0x434C49434B" => return CountingMethod::AllPasses,
+                "both" => return CountingMethod::Both,
                 _ => {
                     eprintln!("Warning: Unknown method '{}', using default (all passes)", args[i + 1]);
                     return CountingMethod::AllPasses;
@@ -58,9 +58,34 @@ fn parse_counting_method() -> CountingMethod {
     CountingMethod::AllPasses
 }
 
+/// Parse command-line arguments to determine the maximum allowed distance
+///
+/// # Returns
+///
+/// The cap given with `--max-distance`, or `None` if no cap was requested.
+fn parse_max_distance() -> Option<u32> {
+    let args: Vec<String> = env::args().collect();
+
+    // Check for --max-distance flag
+    for i in 0..args.len() {
+        if args[i] == "--max-distance" && i + 1 < args.len() {
+            match args[i + 1].parse::<u32>() {
+                Ok(max) => return Some(max),
+                Err(_) => {
+                    eprintln!("Warning: Invalid max distance '{}', ignoring", args[i + 1]);
+                    return None;
+                }
+            }
+        }
+    }
+
+    None
+}
+
 fn main() {
     // Parse command-line arguments to determine counting method
     let method = parse_counting_method();
+    let max_distance = parse_max_distance();
 
     // Read input.txt file
     let input = match read_input_file("input.txt") {
@@ -72,7 +97,7 @@ fn main() {
     };
 
     // Parse rotations
-    let rotations = match parse_rotations(&input) {
+    let rotations = match parse_rotations_with_max(&input, max_distance) {
         Ok(rots) => rots,
         Err(e) => {
             eprintln!("Error parsing rotations: {}", e);
@@ -80,19 +105,14 @@ fn main() {
         }
     };
 
-    // Count zeros using the selected method
-    let count = match method {
-        CountingMethod::EndOfRotation => count_zero_crossings(&rotations),
-        CountingMethod::AllPasses => count_all_zero_passes(&rotations),
-    };
-
-    // Print result to stdout with indication of method used
-    match method {
-        CountingMethod::EndOfRotation => {
-            println!("{} (original method: end-of-rotation crossings)", count);
-        }
-        CountingMethod::AllPasses => {
-            println!("{} (method 0x434C49434B: all passes through 0)", count);
-        }
+    // Count zeros using the selected method and print each result to stdout
+    // with an indication of the method used
+    if matches!(method, CountingMethod::EndOfRotation | CountingMethod::Both) {
+        let count = count_zero_crossings(&rotations);
+        println!("{} (original method: end-of-rotation crossings)", count);
+    }
+    if matches!(method, CountingMethod::AllPasses | CountingMethod::Both) {
+        let count = count_all_zero_passes(&rotations);
+        println!("{} (method 0x434C49434B: all passes through 0)", count);
     }
 }
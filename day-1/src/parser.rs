@@ -16,6 +16,31 @@ pub struct Rotation {
     pub distance: u32,
 }
 
+impl Rotation {
+    /// Create a rotation with the given direction and distance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use safe_dial_rotation::parser::{Direction, Rotation};
+    /// let rotation = Rotation::new(Direction::Left, 68);
+    /// assert_eq!(rotation, Rotation::left(68));
+    /// ```
+    pub fn new(direction: Direction, distance: u32) -> Self {
+        Rotation { direction, distance }
+    }
+
+    /// Create a left rotation by `distance` steps
+    pub fn left(distance: u32) -> Self {
+        Rotation::new(Direction::Left, distance)
+    }
+
+    /// Create a right rotation by `distance` steps
+    pub fn right(distance: u32) -> Self {
+        Rotation::new(Direction::Right, distance)
+    }
+}
+
 /// Errors that can occur during parsing
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseError {
@@ -25,6 +50,8 @@ pub enum ParseError {
     MissingDistance,
     /// Distance value is not a valid unsigned integer
     InvalidDistance(String),
+    /// Distance value is a number but exceeds the allowed maximum
+    DistanceTooLarge { value: u64, line: String },
 }
 
 impl fmt::Display for ParseError {
@@ -39,6 +66,9 @@ impl fmt::Display for ParseError {
             ParseError::InvalidDistance(value) => {
                 write!(f, "Invalid distance value: '{}'. Expected a positive integer.", value)
             }
+            ParseError::DistanceTooLarge { value, line } => {
+                write!(f, "Distance {} in line '{}' exceeds the maximum allowed distance.", value, line)
+            }
         }
     }
 }
@@ -47,7 +77,9 @@ impl std::error::Error for ParseError {}
 
 /// Parse a single rotation instruction line
 ///
-/// Expected format: "[L|R][distance]" where L/R is the direction and distance is a positive integer
+/// Expected format: "[L|R][distance]" where L/R is the direction and distance is a positive integer.
+/// Whitespace between the direction and the distance is allowed, so "R 25" parses like "R25",
+/// but the distance itself must not contain spaces.
 ///
 /// # Examples
 ///
@@ -58,6 +90,26 @@ impl std::error::Error for ParseError {}
 /// assert_eq!(rotation.distance, 25);
 /// ```
 pub fn parse_rotation_line(line: &str) -> Result<Rotation, ParseError> {
+    parse_rotation_line_with_max(line, None)
+}
+
+/// Parse a single rotation instruction line, rejecting distances above a cap
+///
+/// Distances that are numeric but larger than `max_distance` (or too large to
+/// fit in a `u32` when no cap is given) produce `DistanceTooLarge`, while
+/// non-numeric distances still produce `InvalidDistance`.
+///
+/// # Examples
+///
+/// ```
+/// # use safe_dial_rotation::parser::{parse_rotation_line_with_max, ParseError};
+/// assert!(parse_rotation_line_with_max("R100", Some(100)).is_ok());
+/// assert!(matches!(
+///     parse_rotation_line_with_max("R101", Some(100)),
+///     Err(ParseError::DistanceTooLarge { value: 101, .. })
+/// ));
+/// ```
+pub fn parse_rotation_line_with_max(line: &str, max_distance: Option<u32>) -> Result<Rotation, ParseError> {
     let line = line.trim();
 
     if line.is_empty() {
@@ -72,18 +124,24 @@ pub fn parse_rotation_line(line: &str) -> Result<Rotation, ParseError> {
         _ => return Err(ParseError::InvalidDirection(line.to_string())),
     };
 
-    // Extract remaining characters as distance
-    let distance_str = &line[1..];
+    // Extract remaining characters as distance, allowing spaces after the direction
+    let distance_str = line[1..].trim_start();
 
     if distance_str.is_empty() {
         return Err(ParseError::MissingDistance);
     }
 
-    // Parse distance as u32
-    let distance = distance_str.parse::<u32>()
+    // Parse distance as u64 first so that oversized numbers can be told apart
+    // from values that are not numbers at all
+    let value = distance_str.parse::<u64>()
         .map_err(|_| ParseError::InvalidDistance(distance_str.to_string()))?;
 
-    Ok(Rotation { direction, distance })
+    let cap = max_distance.unwrap_or(u32::MAX);
+    if value > cap as u64 {
+        return Err(ParseError::DistanceTooLarge { value, line: line.to_string() });
+    }
+
+    Ok(Rotation { direction, distance: value as u32 })
 }
 
 /// Parse multiple rotation instruction lines
@@ -100,6 +158,14 @@ pub fn parse_rotation_line(line: &str) -> Result<Rotation, ParseError> {
 /// assert_eq!(rotations.len(), 3);
 /// ```
 pub fn parse_rotations(input: &str) -> Result<Vec<Rotation>, String> {
+    parse_rotations_with_max(input, None)
+}
+
+/// Parse multiple rotation instruction lines, rejecting distances above a cap
+///
+/// Behaves like `parse_rotations`, but any distance greater than
+/// `max_distance` is reported as `DistanceTooLarge` along with its line number.
+pub fn parse_rotations_with_max(input: &str, max_distance: Option<u32>) -> Result<Vec<Rotation>, String> {
     let mut rotations = Vec::new();
 
     for (line_num, line) in input.lines().enumerate() {
@@ -111,7 +177,7 @@ pub fn parse_rotations(input: &str) -> Result<Vec<Rotation>, String> {
         }
 
         // Parse the line and add context about line number on error
-        match parse_rotation_line(trimmed) {
+        match parse_rotation_line_with_max(trimmed, max_distance) {
             Ok(rotation) => rotations.push(rotation),
             Err(e) => {
                 return Err(format!("Error on line {}: {}", line_num + 1, e));
@@ -122,6 +188,120 @@ pub fn parse_rotations(input: &str) -> Result<Vec<Rotation>, String> {
     Ok(rotations)
 }
 
+/// Parse multiple rotation instruction lines, skipping any that are invalid
+///
+/// Returns every rotation that parsed, in input order, together with the
+/// 1-based line number and error for each line that did not. Empty lines are
+/// skipped without being reported. The rotations match what `parse_rotations`
+/// returns for the same input with the bad lines removed.
+///
+/// # Examples
+///
+/// ```
+/// # use safe_dial_rotation::parser::{parse_rotations_lenient, Rotation};
+/// let (rotations, errors) = parse_rotations_lenient("R25\nX1\nL10");
+/// assert_eq!(rotations, vec![Rotation::right(25), Rotation::left(10)]);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 2);
+/// ```
+pub fn parse_rotations_lenient(input: &str) -> (Vec<Rotation>, Vec<(usize, ParseError)>) {
+    let mut rotations = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+
+        // Skip empty lines
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match parse_rotation_line(trimmed) {
+            Ok(rotation) => rotations.push(rotation),
+            Err(e) => errors.push((line_num + 1, e)),
+        }
+    }
+
+    (rotations, errors)
+}
+
+/// Parse blocks of rotation instructions separated by blank lines
+///
+/// Each block drives its own independent dial. Consecutive blank lines are
+/// treated as a single separator. Errors report the line number within the
+/// original input, not the index within the block.
+///
+/// # Examples
+///
+/// ```
+/// # use safe_dial_rotation::parser::parse_dial_blocks;
+/// let input = "R25\nL10\n\nR5";
+/// let blocks = parse_dial_blocks(input).unwrap();
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks[0].len(), 2);
+/// assert_eq!(blocks[1].len(), 1);
+/// ```
+pub fn parse_dial_blocks(input: &str) -> Result<Vec<Vec<Rotation>>, String> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+
+        // A blank line closes the current block
+        if trimmed.is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        match parse_rotation_line(trimmed) {
+            Ok(rotation) => current.push(rotation),
+            Err(e) => {
+                return Err(format!("Error on line {}: {}", line_num + 1, e));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    Ok(blocks)
+}
+
+/// Combine consecutive rotations in the same direction into one
+///
+/// Each run of same-direction rotations becomes a single rotation whose
+/// distance is the sum of the run, saturating at `u32::MAX`. The dial ends in
+/// the same place (unless a sum saturated), but the rotations in between are
+/// gone, so counts that depend on where the dial lands after each rotation,
+/// such as `count_zero_crossings`, generally change. Use this for net
+/// movement analysis rather than before counting zeros.
+///
+/// # Examples
+///
+/// ```
+/// # use safe_dial_rotation::parser::{coalesce_rotations, Rotation};
+/// let rotations = vec![Rotation::right(10), Rotation::right(5), Rotation::left(3)];
+/// assert_eq!(coalesce_rotations(&rotations), vec![Rotation::right(15), Rotation::left(3)]);
+/// ```
+pub fn coalesce_rotations(rotations: &[Rotation]) -> Vec<Rotation> {
+    let mut coalesced: Vec<Rotation> = Vec::new();
+
+    for rotation in rotations {
+        match coalesced.last_mut() {
+            Some(last) if last.direction == rotation.direction => {
+                last.distance = last.distance.saturating_add(rotation.distance);
+            }
+            _ => coalesced.push(rotation.clone()),
+        }
+    }
+
+    coalesced
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,4 +336,89 @@ mod tests {
                 "Distance should be preserved: input={}", input);
         }
     }
+
+    #[test]
+    fn test_parse_dial_blocks_splits_on_blank_lines() {
+        let input = "R25\nL10\n\n\nR5\n\nL1\nL2\nL3\n";
+        let blocks = parse_dial_blocks(input).unwrap();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].len(), 2);
+        assert_eq!(blocks[1], vec![Rotation::right(5)]);
+        assert_eq!(blocks[2].len(), 3);
+    }
+
+    #[test]
+    fn test_parse_dial_blocks_reports_original_line_number() {
+        let input = "R25\nL10\n\nR5\nX7";
+        let err = parse_dial_blocks(input).unwrap_err();
+
+        assert!(err.starts_with("Error on line 5:"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_distance_above_cap_is_too_large() {
+        let err = parse_rotation_line_with_max("L500", Some(100)).unwrap_err();
+
+        assert_eq!(err, ParseError::DistanceTooLarge { value: 500, line: "L500".to_string() });
+    }
+
+    #[test]
+    fn test_distance_overflowing_u32_is_too_large() {
+        let err = parse_rotation_line("R5000000000").unwrap_err();
+
+        assert_eq!(err, ParseError::DistanceTooLarge { value: 5_000_000_000, line: "R5000000000".to_string() });
+    }
+
+    #[test]
+    fn test_non_numeric_distance_is_still_invalid() {
+        let err = parse_rotation_line_with_max("R12x", Some(100)).unwrap_err();
+
+        assert_eq!(err, ParseError::InvalidDistance("12x".to_string()));
+    }
+
+    #[test]
+    fn test_parse_rotations_with_max_reports_line_number() {
+        let err = parse_rotations_with_max("R10\nL20\nR1000", Some(999)).unwrap_err();
+
+        assert!(err.starts_with("Error on line 3: Distance 1000"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_parse_rotations_lenient_matches_strict_on_clean_lines() {
+        let noisy = "R25\nbogus\n\nL10\nR\nL5\n";
+        let clean = "R25\n\nL10\nL5\n";
+
+        let (rotations, errors) = parse_rotations_lenient(noisy);
+
+        assert_eq!(rotations, parse_rotations(clean).unwrap());
+        assert_eq!(errors, vec![
+            (2, ParseError::InvalidDirection("bogus".to_string())),
+            (5, ParseError::MissingDistance),
+        ]);
+    }
+
+    #[test]
+    fn test_coalesce_rotations_merges_same_direction_runs() {
+        let rotations = vec![Rotation::right(10), Rotation::right(5), Rotation::left(3)];
+        assert_eq!(coalesce_rotations(&rotations), vec![Rotation::right(15), Rotation::left(3)]);
+
+        let alternating = vec![Rotation::left(1), Rotation::right(2), Rotation::left(3)];
+        assert_eq!(coalesce_rotations(&alternating), alternating);
+        assert!(coalesce_rotations(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_coalesce_rotations_caps_at_u32_max() {
+        let rotations = vec![Rotation::left(u32::MAX), Rotation::left(7)];
+        assert_eq!(coalesce_rotations(&rotations), vec![Rotation::left(u32::MAX)]);
+    }
+
+    #[test]
+    fn test_whitespace_between_direction_and_distance() {
+        assert_eq!(parse_rotation_line("R 25").unwrap(), parse_rotation_line("R25").unwrap());
+        assert_eq!(parse_rotation_line("L \t 7").unwrap(), Rotation::left(7));
+        assert_eq!(parse_rotation_line("R ").unwrap_err(), ParseError::MissingDistance);
+        assert_eq!(parse_rotation_line("R2 5").unwrap_err(), ParseError::InvalidDistance("2 5".to_string()));
+    }
 }
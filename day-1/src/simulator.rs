@@ -14,6 +14,16 @@ impl Dial {
         Dial { position: 50 }
     }
 
+    /// Return the position the dial currently points at (0-99)
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// Move the dial directly to a position, wrapping values of 100 or more
+    pub fn set_position(&mut self, pos: u32) {
+        self.position = pos % 100;
+    }
+
     /// Apply a rotation to the dial and return the new position
     ///
     /// Right rotations add to the position, left rotations subtract.
@@ -34,6 +44,12 @@ impl Dial {
     }
 }
 
+impl Default for Dial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Count how many times the dial passes through position 0 during a single rotation
 ///
 /// We count how many times we visit position 0 (not including the starting position).
@@ -75,6 +91,61 @@ pub fn count_zeros_through_rotation(start_pos: u32, rotation: &Rotation) -> u32
     }
 }
 
+/// Count the steps needed to reach position 0 going right and going left
+///
+/// The position is taken modulo 100, and both distances are below 100, so a
+/// dial already at 0 needs `(0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// # use safe_dial_rotation::simulator::distance_to_zero;
+/// assert_eq!(distance_to_zero(97), (3, 97));
+/// assert_eq!(distance_to_zero(0), (0, 0));
+/// ```
+pub fn distance_to_zero(position: u32) -> (u32, u32) {
+    let left = position % 100;
+    let right = (100 - left) % 100;
+    (right, left)
+}
+
+/// Find the step within a rotation at which the dial first reaches position 0
+///
+/// Returns the 1-based number of unit steps taken when 0 is first reached, or
+/// `None` if the rotation never reaches it. Starting at 0 does not count, so
+/// from 0 the first visit is after a full turn of 100 steps.
+pub fn first_zero_step(start_pos: u32, rotation: &Rotation) -> Option<u32> {
+    // Steps until the first visit to 0, using the same offsets as count_zeros_through_rotation
+    let (right_steps, left_steps) = distance_to_zero(start_pos);
+    let first_visit = match (rotation.direction, start_pos) {
+        (_, 0) => 100,
+        (Direction::Right, _) => right_steps,
+        (Direction::Left, _) => left_steps,
+    };
+
+    if rotation.distance >= first_visit {
+        Some(first_visit)
+    } else {
+        None
+    }
+}
+
+/// Find the single shortest rotation that moves the dial from `start` to `target`
+///
+/// Both positions are taken modulo 100. The distance is always below 100, and
+/// when both directions are equally short (a distance of 50, or 0 when already
+/// at the target) the rotation goes right.
+pub fn shortest_rotation_to(start: u32, target: u32) -> Rotation {
+    // Reaching `target` from `start` takes as many steps as reaching 0 from their difference
+    let (right_distance, left_distance) = distance_to_zero(start % 100 + 100 - target % 100);
+
+    if right_distance <= left_distance {
+        Rotation::right(right_distance)
+    } else {
+        Rotation::left(left_distance)
+    }
+}
+
 /// Count how many times the dial points at position 0 after applying rotations
 ///
 /// The initial position (50) is not counted as a zero crossing.
@@ -98,7 +169,15 @@ pub fn count_zero_crossings(rotations: &[Rotation]) -> u32 {
 /// This counts every pass through 0 during each rotation, not just when ending at 0.
 /// The initial position (50) is not counted.
 pub fn count_all_zero_passes(rotations: &[Rotation]) -> u32 {
-    let mut dial = Dial::new();
+    count_zeros_through_rotations_from(&mut Dial::new(), rotations)
+}
+
+/// Count all passes through 0 while advancing a caller-provided dial
+///
+/// Works like `count_all_zero_passes` but starts from the dial's current
+/// position and leaves the dial at its final position, so a long sequence can
+/// be processed in segments and the counts added together.
+pub fn count_zeros_through_rotations_from(dial: &mut Dial, rotations: &[Rotation]) -> u32 {
     let mut total_passes = 0;
 
     for rotation in rotations {
@@ -112,3 +191,253 @@ pub fn count_all_zero_passes(rotations: &[Rotation]) -> u32 {
 
     total_passes
 }
+
+/// Count all passes through position 0, split by rotation direction
+///
+/// Returns `(right_passes, left_passes)`: the passes made during right
+/// (clockwise) rotations and during left (counterclockwise) rotations. Their
+/// sum always equals `count_all_zero_passes` for the same sequence.
+pub fn count_zero_passes_by_direction(rotations: &[Rotation]) -> (u32, u32) {
+    let mut dial = Dial::new();
+    let mut right_passes = 0;
+    let mut left_passes = 0;
+
+    for rotation in rotations {
+        // Advance the shared dial one rotation at a time so each pass is attributed to its direction
+        let passes = count_zeros_through_rotations_from(&mut dial, std::slice::from_ref(rotation));
+        match rotation.direction {
+            Direction::Right => right_passes += passes,
+            Direction::Left => left_passes += passes,
+        }
+    }
+
+    (right_passes, left_passes)
+}
+
+/// Count end-of-rotation zero crossings for several independent dials
+///
+/// Each block of rotations drives its own dial starting at position 50.
+/// Returns one count per block, in input order.
+pub fn count_zero_crossings_per_dial(blocks: &[Vec<Rotation>]) -> Vec<u32> {
+    blocks
+        .iter()
+        .map(|rotations| count_zero_crossings(rotations))
+        .collect()
+}
+
+/// Compute the dial's unwrapped signed offset from its start after a sequence
+///
+/// Right distances count positively and left distances negatively, with no
+/// wraparound at 100. A positive result means the dial has net-rotated right
+/// (clockwise), a negative result means it has net-rotated left.
+pub fn net_offset(rotations: &[Rotation]) -> i64 {
+    rotations
+        .iter()
+        .map(|rotation| match rotation.direction {
+            Direction::Right => rotation.distance as i64,
+            Direction::Left => -(rotation.distance as i64),
+        })
+        .sum()
+}
+
+/// Count the complete revolutions the dial makes over a sequence
+///
+/// Sums the absolute distance of every rotation, regardless of direction, and
+/// divides by the dial size of 100. Unlike `net_offset`, opposite rotations do
+/// not cancel out, and unlike zero passes the result does not depend on where
+/// the dial starts.
+pub fn count_revolutions(rotations: &[Rotation]) -> u64 {
+    let total_distance_traveled: u64 = rotations
+        .iter()
+        .map(|rotation| rotation.distance as u64)
+        .sum();
+
+    total_distance_traveled / 100
+}
+
+/// Multiplier for the rolling checksum (a large prime)
+const CHECKSUM_BASE: u64 = 1_000_000_007;
+
+/// Compute an order-sensitive checksum over a rotation sequence
+///
+/// Each rotation is encoded as `(distance << 1 | direction) + 1` and folded
+/// into a polynomial rolling hash with wrapping arithmetic. The result is
+/// deterministic across runs and platforms, and reordering or altering any
+/// rotation changes the checksum.
+pub fn rotation_checksum(rotations: &[Rotation]) -> u64 {
+    rotations.iter().fold(0u64, |hash, rotation| {
+        let direction_bit = match rotation.direction {
+            Direction::Left => 0,
+            Direction::Right => 1,
+        };
+        let encoded = ((rotation.distance as u64) << 1 | direction_bit) + 1;
+        hash.wrapping_mul(CHECKSUM_BASE).wrapping_add(encoded)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rot(direction: Direction, distance: u32) -> Rotation {
+        Rotation::new(direction, distance)
+    }
+
+    #[test]
+    fn test_count_zero_crossings_per_dial_is_independent() {
+        // Each dial starts fresh at 50, so only the first two blocks land on 0
+        let blocks = vec![
+            vec![rot(Direction::Right, 50)],
+            vec![rot(Direction::Left, 50), rot(Direction::Right, 10)],
+            vec![rot(Direction::Right, 1)],
+        ];
+
+        assert_eq!(count_zero_crossings_per_dial(&blocks), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn test_rotation_checksum_is_deterministic() {
+        let rotations = vec![rot(Direction::Left, 68), rot(Direction::Right, 48)];
+
+        assert_eq!(rotation_checksum(&rotations), rotation_checksum(&rotations.clone()));
+    }
+
+    #[test]
+    fn test_rotation_checksum_detects_single_change() {
+        let original = vec![rot(Direction::Left, 68), rot(Direction::Right, 48), rot(Direction::Left, 5)];
+
+        let mut corrupted_distance = original.clone();
+        corrupted_distance[1].distance = 49;
+        assert_ne!(rotation_checksum(&original), rotation_checksum(&corrupted_distance));
+
+        let mut corrupted_direction = original.clone();
+        corrupted_direction[2].direction = Direction::Right;
+        assert_ne!(rotation_checksum(&original), rotation_checksum(&corrupted_direction));
+    }
+
+    #[test]
+    fn test_rotation_checksum_is_order_sensitive() {
+        let forward = vec![rot(Direction::Left, 68), rot(Direction::Right, 48)];
+        let reversed = vec![rot(Direction::Right, 48), rot(Direction::Left, 68)];
+
+        assert_ne!(rotation_checksum(&forward), rotation_checksum(&reversed));
+    }
+
+    #[test]
+    fn test_net_offset_cancels_opposite_directions() {
+        let rotations = vec![rot(Direction::Right, 200), rot(Direction::Left, 50)];
+        assert_eq!(net_offset(&rotations), 150);
+
+        let rotations = vec![rot(Direction::Left, 250), rot(Direction::Right, 20)];
+        assert_eq!(net_offset(&rotations), -230);
+
+        assert_eq!(net_offset(&[]), 0);
+    }
+
+    #[test]
+    fn test_first_zero_step() {
+        assert_eq!(first_zero_step(97, &rot(Direction::Right, 5)), Some(3));
+        assert_eq!(first_zero_step(97, &rot(Direction::Right, 2)), None);
+        assert_eq!(first_zero_step(5, &rot(Direction::Left, 5)), Some(5));
+        assert_eq!(first_zero_step(0, &rot(Direction::Left, 99)), None);
+        assert_eq!(first_zero_step(0, &rot(Direction::Right, 250)), Some(100));
+    }
+
+    #[test]
+    fn test_count_zero_passes_by_direction_sums_to_total() {
+        // From 50: L68 passes 0 once, L30 none, R48 lands on 0, L5 none, R160 passes twice
+        let rotations = vec![
+            rot(Direction::Left, 68),
+            rot(Direction::Left, 30),
+            rot(Direction::Right, 48),
+            rot(Direction::Left, 5),
+            rot(Direction::Right, 160),
+        ];
+
+        let (right, left) = count_zero_passes_by_direction(&rotations);
+        assert_eq!((right, left), (3, 1));
+        assert_eq!(right + left, count_all_zero_passes(&rotations));
+    }
+
+    #[test]
+    fn test_set_position_wraps() {
+        let mut dial = Dial::new();
+        assert_eq!(dial.position(), 50);
+
+        dial.set_position(7);
+        assert_eq!(dial.position(), 7);
+
+        dial.set_position(250);
+        assert_eq!(dial.position(), 50);
+    }
+
+    #[test]
+    fn test_count_zeros_resumed_in_segments_matches_single_pass() {
+        let rotations = vec![
+            rot(Direction::Left, 68),
+            rot(Direction::Left, 30),
+            rot(Direction::Right, 48),
+            rot(Direction::Left, 5),
+            rot(Direction::Right, 160),
+        ];
+
+        let mut dial = Dial::new();
+        let first = count_zeros_through_rotations_from(&mut dial, &rotations[..2]);
+
+        // Checkpoint the position and resume on a fresh dial
+        let mut resumed = Dial::new();
+        resumed.set_position(dial.position());
+        let second = count_zeros_through_rotations_from(&mut resumed, &rotations[2..]);
+
+        assert_eq!(first + second, count_all_zero_passes(&rotations));
+    }
+
+    #[test]
+    fn test_shortest_rotation_to() {
+        assert_eq!(shortest_rotation_to(50, 40), Rotation::left(10));
+        assert_eq!(shortest_rotation_to(50, 60), Rotation::right(10));
+        assert_eq!(shortest_rotation_to(95, 5), Rotation::right(10));
+        assert_eq!(shortest_rotation_to(5, 95), Rotation::left(10));
+        assert_eq!(shortest_rotation_to(0, 50), Rotation::right(50));
+        assert_eq!(shortest_rotation_to(30, 30), Rotation::right(0));
+    }
+
+    #[test]
+    fn test_shortest_rotation_to_reaches_target() {
+        for start in (0..100).step_by(7) {
+            for target in (0..100).step_by(11) {
+                let rotation = shortest_rotation_to(start, target);
+                let mut dial = Dial::new();
+                dial.set_position(start);
+
+                assert_eq!(dial.rotate(&rotation), target);
+                assert!(rotation.distance <= 50);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_to_zero() {
+        assert_eq!(distance_to_zero(97), (3, 97));
+        assert_eq!(distance_to_zero(3), (97, 3));
+        assert_eq!(distance_to_zero(50), (50, 50));
+        assert_eq!(distance_to_zero(0), (0, 0));
+        assert_eq!(distance_to_zero(197), (3, 97));
+    }
+
+    #[test]
+    fn test_count_revolutions_ignores_direction() {
+        // 150 + 120 + 40 + 95 = 405 steps, so 4 full turns despite a net offset of only 85
+        let rotations = vec![
+            rot(Direction::Right, 150),
+            rot(Direction::Left, 120),
+            rot(Direction::Left, 40),
+            rot(Direction::Right, 95),
+        ];
+
+        assert_eq!(count_revolutions(&rotations), 4);
+        assert_eq!(net_offset(&rotations), 85);
+        assert_eq!(count_revolutions(&[rot(Direction::Left, 99)]), 0);
+        assert_eq!(count_revolutions(&[]), 0);
+    }
+}
@@ -2,9 +2,10 @@
 // Core functionality for parsing and calculating battery joltage
 
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// Represents a single bank of batteries as a sequence of digits
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,7 +16,8 @@ pub struct BatteryBank {
 impl BatteryBank {
     /// Parse a line into a battery bank
     ///
-    /// Returns `None` if the line is empty or contains only whitespace.
+    /// Returns `None` if the line is empty, contains only whitespace, or is a
+    /// comment starting with `#`.
     /// Returns `Err` if the line contains non-digit characters.
     ///
     /// # Arguments
@@ -23,14 +25,14 @@ impl BatteryBank {
     ///
     /// # Returns
     /// * `Ok(Some(BatteryBank))` - Successfully parsed battery bank
-    /// * `Ok(None)` - Empty or whitespace-only line (should be skipped)
+    /// * `Ok(None)` - Empty, whitespace-only or comment line (should be skipped)
     /// * `Err(ParseError)` - Line contains invalid characters
     pub fn from_line(line: &str) -> Result<Option<Self>, ParseError> {
         // Trim the line to check if it's empty or whitespace-only
         let trimmed = line.trim();
 
-        // Handle empty and whitespace-only lines
-        if trimmed.is_empty() {
+        // Handle empty, whitespace-only and comment lines
+        if trimmed.is_empty() || trimmed.starts_with('#') {
             return Ok(None);
         }
 
@@ -54,6 +56,115 @@ impl BatteryBank {
         Ok(Some(BatteryBank { batteries }))
     }
 
+    /// Build a battery bank from a sequence of digit values
+    ///
+    /// Mirrors the validation in `from_line` without going through a string:
+    /// every value must be a digit between 0 and 9.
+    ///
+    /// # Arguments
+    /// * `digits` - The battery values, in bank order
+    ///
+    /// # Returns
+    /// * `Ok(BatteryBank)` - All values were valid digits
+    /// * `Err(ParseError)` - A value was greater than 9
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank::from_digits(vec![9, 8, 7]).unwrap();
+    /// assert_eq!(bank.batteries, vec![9, 8, 7]);
+    /// assert!(BatteryBank::from_digits([1, 10]).is_err());
+    /// ```
+    pub fn from_digits<I: IntoIterator<Item = u8>>(digits: I) -> Result<BatteryBank, ParseError> {
+        let mut batteries = Vec::new();
+
+        for (index, value) in digits.into_iter().enumerate() {
+            if value > 9 {
+                return Err(ParseError::InvalidDigit { index, value });
+            }
+            batteries.push(value);
+        }
+
+        Ok(BatteryBank { batteries })
+    }
+
+    /// Check whether the bank has enough batteries to select `n` of them
+    ///
+    /// This is the precondition of every joltage method taking `n`: when it is
+    /// false they return `JoltageError::InsufficientBatteries`.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![9, 8, 7] };
+    /// assert!(bank.can_select(3));
+    /// assert!(!bank.can_select(4));
+    /// ```
+    pub fn can_select(&self, n: usize) -> bool {
+        self.batteries.len() >= n
+    }
+
+    /// Check whether every battery in the bank is 0
+    ///
+    /// Such banks can only ever produce a joltage of 0. An empty bank is not
+    /// considered all-zero, since it has no batteries to select at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// assert!(BatteryBank { batteries: vec![0, 0, 0] }.is_all_zero());
+    /// assert!(!BatteryBank { batteries: vec![0, 1, 0] }.is_all_zero());
+    /// assert!(!BatteryBank { batteries: vec![] }.is_all_zero());
+    /// ```
+    pub fn is_all_zero(&self) -> bool {
+        !self.batteries.is_empty() && self.batteries.iter().all(|&battery| battery == 0)
+    }
+
+    /// Count how many batteries hold each digit value
+    ///
+    /// Index `d` of the result is the number of batteries with value `d`, so
+    /// the counts always sum to `batteries.len()`. Banks built through
+    /// `from_line` or `from_digits` only hold digits; a value above 9 placed
+    /// in `batteries` directly causes a panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let counts = BatteryBank { batteries: vec![9, 1, 9, 0] }.digit_counts();
+    /// assert_eq!(counts, [1, 1, 0, 0, 0, 0, 0, 0, 0, 2]);
+    /// ```
+    pub fn digit_counts(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for &battery in &self.batteries {
+            counts[battery as usize] += 1;
+        }
+        counts
+    }
+
+    /// Return a new bank with the batteries in reverse order
+    ///
+    /// Mirroring a bank does not generally preserve its maximum joltage, since
+    /// selected batteries are read in bank order: `[1, 9]` gives 19 for n = 2
+    /// while its mirror gives 91. The single-battery maximum is always the same,
+    /// and so is every maximum of a palindromic bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![1, 2, 3] };
+    /// assert_eq!(bank.reversed().batteries, vec![3, 2, 1]);
+    /// ```
+    pub fn reversed(&self) -> BatteryBank {
+        BatteryBank {
+            batteries: self.batteries.iter().rev().copied().collect(),
+        }
+    }
+
     /// Find the maximum joltage that can be produced by selecting two batteries
     ///
     /// Examines all pairs of batteries (i, j) where i < j, calculates the joltage
@@ -72,7 +183,7 @@ impl BatteryBank {
     /// ```
     pub fn find_max_joltage(&self) -> Result<u32, JoltageError> {
         // Check if we have at least 2 batteries
-        if self.batteries.len() < 2 {
+        if !self.can_select(2) {
             return Err(JoltageError::InsufficientBatteries {
                 count: self.batteries.len(),
                 required: 2,
@@ -98,6 +209,33 @@ impl BatteryBank {
         Ok(max_joltage)
     }
 
+    /// Calculate the joltage of every pair of batteries
+    ///
+    /// Returns `batteries[i] * 10 + batteries[j]` for every pair `i < j`, in the
+    /// same order as the double loop in `find_max_joltage`. The maximum of the
+    /// returned values equals `find_max_joltage`. Banks with fewer than 2
+    /// batteries produce an empty vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![1, 9, 4] };
+    /// assert_eq!(bank.all_pair_joltages(), vec![19, 14, 94]);
+    /// ```
+    pub fn all_pair_joltages(&self) -> Vec<u32> {
+        let mut joltages = Vec::new();
+
+        // Iterate through all pairs (i, j) where i < j
+        for i in 0..self.batteries.len() {
+            for j in (i + 1)..self.batteries.len() {
+                joltages.push((self.batteries[i] as u32) * 10 + (self.batteries[j] as u32));
+            }
+        }
+
+        joltages
+    }
+
     /// Find the maximum joltage that can be produced by selecting exactly n batteries
     ///
     /// Uses a greedy algorithm to select n batteries that form the largest possible number.
@@ -109,7 +247,8 @@ impl BatteryBank {
     ///
     /// # Returns
     /// * `Ok(u64)` - The maximum n-digit joltage value
-    /// * `Err(JoltageError)` - If the bank has fewer than n batteries
+    /// * `Err(JoltageError)` - If the bank has fewer than n batteries, or the
+    ///   joltage does not fit in a `u64`
     ///
     /// # Examples
     /// ```
@@ -119,8 +258,216 @@ impl BatteryBank {
     /// assert_eq!(bank.find_max_joltage_n(12).unwrap(), 987654321111);
     /// ```
     pub fn find_max_joltage_n(&self, n: usize) -> Result<u64, JoltageError> {
+        self.find_max_joltage_n_leading(n).map(|(joltage, _)| joltage)
+    }
+
+    /// Find the maximum n-battery joltage and whether its selection has a leading zero
+    ///
+    /// The joltage value is the same as `find_max_joltage_n`. The flag is true when
+    /// the first selected battery is 0, so a "05" selection can be told apart from
+    /// a genuine single-digit 5. Selecting zero batteries never has a leading zero.
+    ///
+    /// # Arguments
+    /// * `n` - The number of batteries to select
+    ///
+    /// # Returns
+    /// * `Ok((u64, bool))` - The maximum joltage and the leading-zero flag
+    /// * `Err(JoltageError)` - If the bank has fewer than n batteries, or the
+    ///   joltage does not fit in a `u64`
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![0, 0, 5] };
+    /// assert_eq!(bank.find_max_joltage_n_leading(2).unwrap(), (5, true));
+    ///
+    /// let bank = BatteryBank { batteries: vec![0, 5, 0] };
+    /// assert_eq!(bank.find_max_joltage_n_leading(2).unwrap(), (50, false));
+    /// ```
+    pub fn find_max_joltage_n_leading(&self, n: usize) -> Result<(u64, bool), JoltageError> {
+        let selected = self.select_max_indices(n)?;
+
+        let leading_zero = selected
+            .first()
+            .is_some_and(|&index| self.batteries[index] == 0);
+
+        // Convert the selected digits to a u64 number, failing if it does not fit
+        let mut joltage = 0u64;
+        for index in selected {
+            joltage = joltage
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(self.batteries[index] as u64))
+                .ok_or(JoltageError::Overflow { required: n })?;
+        }
+
+        Ok((joltage, leading_zero))
+    }
+
+    /// Find the maximum joltage from a contiguous run of exactly n batteries
+    ///
+    /// Unlike `find_max_joltage_n`, which may skip batteries, the n selected
+    /// batteries must sit next to each other in the bank. Every window is also a
+    /// valid subsequence, so the result never exceeds `find_max_joltage_n`; the
+    /// two are equal exactly when some window reaches the subsequence maximum,
+    /// meaning the selection rule makes no difference for this bank.
+    ///
+    /// # Arguments
+    /// * `n` - The number of adjacent batteries to select
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The largest n-digit value formed by any window
+    /// * `Err(JoltageError)` - If the bank has fewer than n batteries, or the
+    ///   value does not fit in a `u64`
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![9, 1, 8, 7] };
+    /// assert_eq!(bank.max_contiguous_joltage_n(2).unwrap(), 91);
+    /// assert_eq!(bank.find_max_joltage_n(2).unwrap(), 98);
+    /// ```
+    pub fn max_contiguous_joltage_n(&self, n: usize) -> Result<u64, JoltageError> {
+        if !self.can_select(n) {
+            return Err(JoltageError::InsufficientBatteries {
+                count: self.batteries.len(),
+                required: n,
+            });
+        }
+
+        if n == 0 {
+            return Ok(0);
+        }
+
+        // Windows all have the same length, so comparing digit slices
+        // lexicographically gives the same order as comparing their values
+        let best = self
+            .batteries
+            .windows(n)
+            .max()
+            .expect("bank has at least n batteries");
+
+        let mut joltage = 0u64;
+        for &battery in best {
+            joltage = joltage
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(battery as u64))
+                .ok_or(JoltageError::Overflow { required: n })?;
+        }
+
+        Ok(joltage)
+    }
+
+    /// Check whether exactly one selection of n batteries produces the maximum joltage
+    ///
+    /// "Unique" means there is exactly one strictly increasing sequence of n
+    /// battery indices whose digits, read in order, equal the digits chosen by
+    /// `find_max_joltage_n`. A tie for the maximum digit in a greedy window only
+    /// makes the result ambiguous if the alternative index can still be completed
+    /// to the same value: for `[9, 9, 1]` with n = 2 the tie at the first step is
+    /// harmless, because only indices (0, 1) give 99. Selecting zero batteries is
+    /// always unique.
+    ///
+    /// # Arguments
+    /// * `n` - The number of batteries to select
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - True if exactly one index sequence yields the maximum
+    /// * `Err(JoltageError)` - If the bank has fewer than n batteries
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![9, 9, 1] };
+    /// assert!(bank.max_joltage_n_is_unique(2).unwrap());
+    ///
+    /// // Indices (0, 2), (0, 3) and (2, 3) all give 99
+    /// let bank = BatteryBank { batteries: vec![9, 1, 9, 9] };
+    /// assert!(!bank.max_joltage_n_is_unique(2).unwrap());
+    /// ```
+    pub fn max_joltage_n_is_unique(&self, n: usize) -> Result<bool, JoltageError> {
+        let target: Vec<u8> = self
+            .select_max_indices(n)?
+            .into_iter()
+            .map(|index| self.batteries[index])
+            .collect();
+
+        // ways[k] = number of index sequences matching the first k target digits,
+        // capped at 2 since only "one" versus "more than one" matters
+        let mut ways = vec![0u8; n + 1];
+        ways[0] = 1;
+
+        for &battery in &self.batteries {
+            // Walk backwards so each battery extends a prefix at most once
+            for k in (1..=n).rev() {
+                if target[k - 1] == battery {
+                    ways[k] = (ways[k] + ways[k - 1]).min(2);
+                }
+            }
+        }
+
+        Ok(ways[n] == 1)
+    }
+
+    /// Find the maximum joltage when the n selected batteries are multiplied together
+    ///
+    /// Since every battery is a digit from 0 to 9, the product is maximized by
+    /// picking the n largest values regardless of their order in the bank. A zero
+    /// is only selected when fewer than n non-zero batteries exist, in which case
+    /// the product is 0. Selecting zero batteries gives the empty product, 1.
+    ///
+    /// # Arguments
+    /// * `n` - The number of batteries to select
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The maximum product of n batteries
+    /// * `Err(JoltageError)` - If the bank has fewer than n batteries, or the
+    ///   product does not fit in a `u64`
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::BatteryBank;
+    ///
+    /// let bank = BatteryBank { batteries: vec![2, 9, 1, 5] };
+    /// assert_eq!(bank.find_max_product_joltage(2).unwrap(), 45);
+    /// ```
+    pub fn find_max_product_joltage(&self, n: usize) -> Result<u64, JoltageError> {
+        // Check if we have at least n batteries
+        if !self.can_select(n) {
+            return Err(JoltageError::InsufficientBatteries {
+                count: self.batteries.len(),
+                required: n,
+            });
+        }
+
+        // Pick the n largest batteries
+        let mut sorted = self.batteries.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut product = 1u64;
+        for &battery in &sorted[..n] {
+            // A zero forces the product to 0, so no further overflow is possible
+            if battery == 0 {
+                return Ok(0);
+            }
+            product = product
+                .checked_mul(battery as u64)
+                .ok_or(JoltageError::Overflow { required: n })?;
+        }
+
+        Ok(product)
+    }
+
+    /// Greedily select the indices of the n batteries forming the largest number
+    ///
+    /// Works left-to-right, selecting the largest digit available at each position
+    /// while ensuring enough batteries remain to fill the remaining positions. Ties
+    /// are resolved in favour of the earliest index.
+    fn select_max_indices(&self, n: usize) -> Result<Vec<usize>, JoltageError> {
         // Check if we have at least n batteries
-        if self.batteries.len() < n {
+        if !self.can_select(n) {
             return Err(JoltageError::InsufficientBatteries {
                 count: self.batteries.len(),
                 required: n,
@@ -129,7 +476,7 @@ impl BatteryBank {
 
         // Greedy algorithm: for each position in the result, find the largest digit
         // we can place there while leaving enough batteries for the remaining positions
-        let mut result = Vec::with_capacity(n);
+        let mut selected = Vec::with_capacity(n);
         let mut start_index = 0;
 
         for position in 0..n {
@@ -150,20 +497,14 @@ impl BatteryBank {
                 }
             }
 
-            // Add this digit to our result
-            result.push(max_digit);
+            // Record the selected battery
+            selected.push(max_index);
 
             // Next search starts after this selected battery
             start_index = max_index + 1;
         }
 
-        // Convert the result vector to a u64 number
-        let mut joltage = 0u64;
-        for digit in result {
-            joltage = joltage * 10 + (digit as u64);
-        }
-
-        Ok(joltage)
+        Ok(selected)
     }
 }
 
@@ -172,6 +513,8 @@ impl BatteryBank {
 pub enum ParseError {
     FileNotFound(PathBuf),
     InvalidCharacter { line: usize, character: char },
+    InvalidDigit { index: usize, value: u8 },
+    InvalidHeader(String),
     IoError(io::Error),
 }
 
@@ -188,6 +531,16 @@ impl fmt::Display for ParseError {
                     character, line
                 )
             }
+            ParseError::InvalidDigit { index, value } => {
+                write!(
+                    f,
+                    "Invalid digit value {} at position {}, expected 0-9",
+                    value, index
+                )
+            }
+            ParseError::InvalidHeader(header) => {
+                write!(f, "Invalid header '{}', expected '#n=<number greater than 0>'", header)
+            }
             ParseError::IoError(err) => {
                 write!(f, "I/O error while reading input: {}", err)
             }
@@ -214,6 +567,7 @@ impl From<io::Error> for ParseError {
 #[derive(Debug)]
 pub enum JoltageError {
     InsufficientBatteries { count: usize, required: usize },
+    Overflow { required: usize },
 }
 
 impl fmt::Display for JoltageError {
@@ -226,6 +580,13 @@ impl fmt::Display for JoltageError {
                     count, required
                 )
             }
+            JoltageError::Overflow { required } => {
+                write!(
+                    f,
+                    "Joltage for {} selected batteries does not fit in a 64-bit value",
+                    required
+                )
+            }
         }
     }
 }
@@ -236,6 +597,7 @@ impl std::error::Error for JoltageError {}
 #[derive(Debug)]
 pub enum ProcessingError {
     ParseError(ParseError),
+    FileError { path: PathBuf, error: ParseError },
     JoltageError { bank_index: usize, error: JoltageError },
 }
 
@@ -245,6 +607,9 @@ impl fmt::Display for ProcessingError {
             ProcessingError::ParseError(err) => {
                 write!(f, "Parse error: {}", err)
             }
+            ProcessingError::FileError { path, error } => {
+                write!(f, "Parse error in {}: {}", path.display(), error)
+            }
             ProcessingError::JoltageError { bank_index, error } => {
                 write!(f, "Error in bank {}: {}", bank_index, error)
             }
@@ -256,6 +621,7 @@ impl std::error::Error for ProcessingError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             ProcessingError::ParseError(err) => Some(err),
+            ProcessingError::FileError { error, .. } => Some(error),
             ProcessingError::JoltageError { error, .. } => Some(error),
         }
     }
@@ -282,6 +648,91 @@ pub struct ProcessingResult {
     pub errors: Vec<ProcessingError>,
 }
 
+impl ProcessingResult {
+    /// Number of banks whose joltage was calculated successfully
+    pub fn success_count(&self) -> usize {
+        self.bank_results.len()
+    }
+
+    /// Number of banks that produced an error
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Total number of banks processed
+    ///
+    /// Every bank ends up in exactly one of `bank_results` or `errors`, so this
+    /// is always `success_count() + error_count()`.
+    pub fn total_count(&self) -> usize {
+        self.success_count() + self.error_count()
+    }
+
+    /// Fraction of banks processed successfully, between 0.0 and 1.0
+    ///
+    /// Returns 0.0 when no banks were processed.
+    pub fn success_rate(&self) -> f64 {
+        if self.total_count() == 0 {
+            return 0.0;
+        }
+        self.success_count() as f64 / self.total_count() as f64
+    }
+
+    /// Export the results as CSV text for spreadsheet import
+    ///
+    /// Emits a `bank_index,max_joltage` header followed by one row per successful
+    /// bank, using 1-based bank indices to match the CLI display. Errored banks
+    /// have no row; instead an `errors,<count>` line records how many there were.
+    /// The final line is `total,<total_joltage>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::{BatteryBank, calculate_total_joltage};
+    ///
+    /// let banks = vec![BatteryBank { batteries: vec![9, 8, 7] }];
+    /// let csv = calculate_total_joltage(&banks).to_csv();
+    /// assert_eq!(csv, "bank_index,max_joltage\n1,98\nerrors,0\ntotal,98\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("bank_index,max_joltage\n");
+
+        for bank_result in &self.bank_results {
+            csv.push_str(&format!("{},{}\n", bank_result.bank_index + 1, bank_result.max_joltage));
+        }
+
+        csv.push_str(&format!("errors,{}\n", self.errors.len()));
+        csv.push_str(&format!("total,{}\n", self.total_joltage));
+
+        csv
+    }
+
+    /// The `k` successful banks with the highest joltage, highest first
+    ///
+    /// Ties are broken by lower `bank_index`. If fewer than `k` banks succeeded,
+    /// all of them are returned. The result itself is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use battery_joltage::{BatteryBank, calculate_total_joltage};
+    ///
+    /// let banks = vec![
+    ///     BatteryBank { batteries: vec![5, 4] },
+    ///     BatteryBank { batteries: vec![9, 8] },
+    ///     BatteryBank { batteries: vec![7, 1] },
+    /// ];
+    /// let result = calculate_total_joltage(&banks);
+    /// let top: Vec<usize> = result.top_k(2).iter().map(|r| r.bank_index).collect();
+    /// assert_eq!(top, vec![1, 2]);
+    /// ```
+    pub fn top_k(&self, k: usize) -> Vec<&BankResult> {
+        let mut ranked: Vec<&BankResult> = self.bank_results.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.max_joltage.cmp(&a.max_joltage).then(a.bank_index.cmp(&b.bank_index))
+        });
+        ranked.truncate(k);
+        ranked
+    }
+}
+
 /// Calculate the total joltage across all battery banks
 ///
 /// Processes each bank sequentially, calculating the maximum joltage for each.
@@ -339,89 +790,285 @@ pub fn calculate_total_joltage(banks: &[BatteryBank]) -> ProcessingResult {
 /// assert_eq!(result.errors.len(), 0);
 /// ```
 pub fn calculate_total_joltage_n(banks: &[BatteryBank], n: usize) -> ProcessingResult {
-    let mut bank_results = Vec::new();
-    let mut total_joltage = 0u64;
-    let mut errors = Vec::new();
-
-    // Process each bank sequentially
-    for (index, bank) in banks.iter().enumerate() {
-        let result = if n == 2 {
-            // Use the optimized 2-battery algorithm
-            bank.find_max_joltage().map(|v| v as u64)
-        } else {
-            // Use the n-battery algorithm
-            bank.find_max_joltage_n(n)
-        };
-
-        match result {
-            Ok(max_joltage) => {
-                // Successfully calculated joltage - add to results
-                bank_results.push(BankResult {
-                    bank_index: index,
-                    max_joltage,
-                });
-                total_joltage += max_joltage;
-            }
-            Err(error) => {
-                // Bank produced an error - collect it and continue
-                errors.push(ProcessingError::JoltageError {
-                    bank_index: index,
-                    error,
-                });
-            }
-        }
-    }
-
-    ProcessingResult {
-        bank_results,
-        total_joltage,
-        errors,
-    }
+    collect_bank_results(banks.iter().map(|bank| bank_joltage_n(bank, n)))
 }
 
-/// Parse an input file containing battery banks
+/// Calculate the total joltage using n batteries per bank, timing each bank
 ///
-/// Reads the file line by line using buffered I/O. Each non-empty line
-/// should contain a battery bank (sequence of digits). Empty lines and
-/// whitespace-only lines are skipped.
+/// Produces exactly the same `ProcessingResult` as `calculate_total_joltage_n`.
+/// The durations are in bank order with one entry per bank, including banks
+/// that produced an error, and cover only the joltage calculation itself.
 ///
 /// # Arguments
-/// * `path` - Path to the input file
+/// * `banks` - A slice of battery banks to process
+/// * `n` - The number of batteries to select from each bank
 ///
 /// # Returns
-/// * `Ok(Vec<BatteryBank>)` - Successfully parsed battery banks
-/// * `Err(ParseError)` - File not found, I/O error, or invalid line content
+/// * `(ProcessingResult, Vec<Duration>)` - The result and the time spent on each bank
 ///
 /// # Examples
-/// ```no_run
-/// use battery_joltage::parse_input_file;
-/// use std::path::Path;
+/// ```
+/// use battery_joltage::{BatteryBank, calculate_total_joltage_n_timed};
 ///
-/// let banks = parse_input_file(Path::new("input.txt")).unwrap();
-/// println!("Parsed {} battery banks", banks.len());
+/// let banks = vec![BatteryBank { batteries: vec![9, 8, 7] }, BatteryBank { batteries: vec![1] }];
+/// let (result, timings) = calculate_total_joltage_n_timed(&banks, 2);
+/// assert_eq!(result.total_joltage, 98);
+/// assert_eq!(timings.len(), 2);
 /// ```
-pub fn parse_input_file(path: &Path) -> Result<Vec<BatteryBank>, ParseError> {
-    // Check if file exists and open it
-    let file = File::open(path).map_err(|err| {
+pub fn calculate_total_joltage_n_timed(banks: &[BatteryBank], n: usize) -> (ProcessingResult, Vec<Duration>) {
+    let mut timings = Vec::with_capacity(banks.len());
+    let mut results = Vec::with_capacity(banks.len());
+
+    for bank in banks {
+        let start = Instant::now();
+        let result = bank_joltage_n(bank, n);
+        timings.push(start.elapsed());
+        results.push(result);
+    }
+
+    (collect_bank_results(results), timings)
+}
+
+/// Find the bank with the highest joltage using n batteries per bank
+///
+/// Computes the same per-bank values as `calculate_total_joltage_n` but only
+/// keeps the best one. Banks that produce errors are ignored.
+///
+/// # Arguments
+/// * `banks` - A slice of battery banks to process
+/// * `n` - The number of batteries to select from each bank
+///
+/// # Returns
+/// * `Some(BankResult)` - The bank with the largest joltage, preferring the
+///   earliest index on ties
+/// * `None` - If there are no banks or every bank produced an error
+///
+/// # Examples
+/// ```
+/// use battery_joltage::{BatteryBank, max_bank_joltage_n};
+///
+/// let banks = vec![
+///     BatteryBank { batteries: vec![1, 2, 3] },
+///     BatteryBank { batteries: vec![9, 1] },
+///     BatteryBank { batteries: vec![5] },
+/// ];
+/// let best = max_bank_joltage_n(&banks, 2).unwrap();
+/// assert_eq!(best.bank_index, 1);
+/// assert_eq!(best.max_joltage, 91);
+/// ```
+pub fn max_bank_joltage_n(banks: &[BatteryBank], n: usize) -> Option<BankResult> {
+    let mut best: Option<BankResult> = None;
+
+    for (index, bank) in banks.iter().enumerate() {
+        let Ok(max_joltage) = bank_joltage_n(bank, n) else {
+            continue;
+        };
+
+        // Only a strictly larger joltage replaces the current best, keeping the earliest on ties
+        if best.as_ref().is_none_or(|current| max_joltage > current.max_joltage) {
+            best = Some(BankResult {
+                bank_index: index,
+                max_joltage,
+            });
+        }
+    }
+
+    best
+}
+
+/// Calculate the maximum joltage of a single bank using n batteries
+fn bank_joltage_n(bank: &BatteryBank, n: usize) -> Result<u64, JoltageError> {
+    if n == 2 {
+        // Use the optimized 2-battery algorithm
+        bank.find_max_joltage().map(|v| v as u64)
+    } else {
+        // Use the n-battery algorithm
+        bank.find_max_joltage_n(n)
+    }
+}
+
+/// Gather per-bank joltage results, in bank order, into a `ProcessingResult`
+///
+/// Banks that produced an error are skipped and their errors are collected
+/// for reporting; the total is the sum of the successful banks.
+fn collect_bank_results<I>(results: I) -> ProcessingResult
+where
+    I: IntoIterator<Item = Result<u64, JoltageError>>,
+{
+    let mut bank_results = Vec::new();
+    let mut total_joltage = 0u64;
+    let mut errors = Vec::new();
+
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(max_joltage) => {
+                // Successfully calculated joltage - add to results
+                bank_results.push(BankResult {
+                    bank_index: index,
+                    max_joltage,
+                });
+                total_joltage += max_joltage;
+            }
+            Err(error) => {
+                // Bank produced an error - collect it and continue
+                errors.push(ProcessingError::JoltageError {
+                    bank_index: index,
+                    error,
+                });
+            }
+        }
+    }
+
+    ProcessingResult {
+        bank_results,
+        total_joltage,
+        errors,
+    }
+}
+
+/// Calculate the total joltage across all battery banks, leaving out all-zero banks
+///
+/// Behaves like `calculate_total_joltage_n`, except that banks for which
+/// `BatteryBank::is_all_zero` is true are left out of `bank_results`. Errors
+/// are kept as they are, so an all-zero bank that is too short is still
+/// reported. Bank indices still refer to positions in `banks`, so omitted banks
+/// leave gaps. Since an all-zero bank can only contribute 0, the total matches
+/// `calculate_total_joltage_n`.
+///
+/// # Arguments
+/// * `banks` - A slice of battery banks to process
+/// * `n` - The number of batteries to select from each bank
+///
+/// # Returns
+/// * `ProcessingResult` - Results for every bank that is not all-zero, and all errors
+///
+/// # Examples
+/// ```
+/// use battery_joltage::{BatteryBank, calculate_total_joltage_n_skip_zero};
+///
+/// let banks = vec![
+///     BatteryBank { batteries: vec![0, 0, 0] },
+///     BatteryBank { batteries: vec![9, 8, 7] },
+/// ];
+/// let result = calculate_total_joltage_n_skip_zero(&banks, 2);
+/// assert_eq!(result.bank_results.len(), 1);
+/// assert_eq!(result.bank_results[0].bank_index, 1);
+/// assert_eq!(result.total_joltage, 98);
+/// ```
+pub fn calculate_total_joltage_n_skip_zero(banks: &[BatteryBank], n: usize) -> ProcessingResult {
+    let mut result = calculate_total_joltage_n(banks, n);
+
+    result.bank_results.retain(|bank_result| !banks[bank_result.bank_index].is_all_zero());
+
+    result
+}
+
+/// Parse several input files and calculate the total joltage across all of them
+///
+/// The banks from every file are concatenated in the order the paths are given,
+/// so bank indices in the result are global across files. The combined banks
+/// are then processed once with `calculate_total_joltage_n`.
+///
+/// # Arguments
+/// * `paths` - The input files to read, in order
+/// * `n` - The number of batteries to select from each bank
+///
+/// # Returns
+/// * `Ok(ProcessingResult)` - Results for every bank across all files
+/// * `Err(ProcessingError::FileError)` - The first file that failed to parse, with its path
+///
+/// # Examples
+/// ```no_run
+/// use battery_joltage::parse_and_total_files;
+///
+/// let result = parse_and_total_files(&["north.txt", "south.txt"], 12).unwrap();
+/// println!("Combined joltage: {}", result.total_joltage);
+/// ```
+pub fn parse_and_total_files<P: AsRef<Path>>(paths: &[P], n: usize) -> Result<ProcessingResult, ProcessingError> {
+    let mut banks = Vec::new();
+
+    for path in paths {
+        let path = path.as_ref();
+        let file_banks = parse_input_file(path).map_err(|error| ProcessingError::FileError {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        banks.extend(file_banks);
+    }
+
+    Ok(calculate_total_joltage_n(&banks, n))
+}
+
+/// Read an input file into a string
+///
+/// A missing file is reported as `FileNotFound`; any other failure as `IoError`.
+fn read_input_file(path: &Path) -> Result<String, ParseError> {
+    fs::read_to_string(path).map_err(|err| {
         if err.kind() == io::ErrorKind::NotFound {
             ParseError::FileNotFound(path.to_path_buf())
         } else {
             ParseError::IoError(err)
         }
-    })?;
+    })
+}
 
-    // Use buffered reader for efficiency
-    let reader = BufReader::new(file);
+/// Parse an input file containing battery banks
+///
+/// Reads the whole file and delegates to `parse_input_str`. Each non-empty
+/// line should contain a battery bank (sequence of digits). Empty lines and
+/// whitespace-only lines are skipped.
+///
+/// # Arguments
+/// * `path` - Path to the input file
+///
+/// # Returns
+/// * `Ok(Vec<BatteryBank>)` - Successfully parsed battery banks
+/// * `Err(ParseError)` - File not found, I/O error, or invalid line content
+///
+/// # Examples
+/// ```no_run
+/// use battery_joltage::parse_input_file;
+/// use std::path::Path;
+///
+/// let banks = parse_input_file(Path::new("input.txt")).unwrap();
+/// println!("Parsed {} battery banks", banks.len());
+/// ```
+pub fn parse_input_file(path: &Path) -> Result<Vec<BatteryBank>, ParseError> {
+    let content = read_input_file(path)?;
+
+    parse_input_str(&content)
+}
+
+/// Parse battery banks from an in-memory string
+///
+/// Each non-empty line should contain a battery bank (sequence of digits).
+/// Empty lines and whitespace-only lines are skipped. Errors report the
+/// 1-based line number within `content`.
+///
+/// # Arguments
+/// * `content` - The input text, one bank per line
+///
+/// # Returns
+/// * `Ok(Vec<BatteryBank>)` - Successfully parsed battery banks
+/// * `Err(ParseError)` - Invalid line content
+///
+/// # Examples
+/// ```
+/// use battery_joltage::parse_input_str;
+///
+/// let banks = parse_input_str("987\n\n123\n").unwrap();
+/// assert_eq!(banks.len(), 2);
+/// assert_eq!(banks[1].batteries, vec![1, 2, 3]);
+/// ```
+pub fn parse_input_str(content: &str) -> Result<Vec<BatteryBank>, ParseError> {
     let mut banks = Vec::new();
-    let mut line_number = 0;
 
     // Process each line
-    for line_result in reader.lines() {
-        line_number += 1;
-        let line = line_result?;
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
 
         // Parse the line into a battery bank
-        match BatteryBank::from_line(&line) {
+        match BatteryBank::from_line(line) {
             Ok(Some(bank)) => {
                 // Valid bank - add it to our collection
                 banks.push(bank);
@@ -447,6 +1094,210 @@ pub fn parse_input_file(path: &Path) -> Result<Vec<BatteryBank>, ParseError> {
     Ok(banks)
 }
 
+/// Parse an input file where banks may wrap across several lines
+///
+/// Reads the whole file and delegates to `parse_input_str_multiline`.
+///
+/// # Arguments
+/// * `path` - Path to the input file
+///
+/// # Returns
+/// * `Ok(Vec<BatteryBank>)` - Successfully parsed battery banks
+/// * `Err(ParseError)` - File not found, I/O error, or invalid line content
+pub fn parse_input_file_multiline(path: &Path) -> Result<Vec<BatteryBank>, ParseError> {
+    let content = read_input_file(path)?;
+
+    parse_input_str_multiline(&content)
+}
+
+/// Parse battery banks that may wrap across several lines
+///
+/// Consecutive non-blank lines are concatenated into a single bank, and one or
+/// more blank lines separate banks. Comment lines starting with `#` are skipped
+/// without ending the current bank. Every line is still validated on its own,
+/// so errors report the 1-based line number of the offending line.
+///
+/// # Arguments
+/// * `content` - The input text, with banks separated by blank lines
+///
+/// # Returns
+/// * `Ok(Vec<BatteryBank>)` - Successfully parsed battery banks
+/// * `Err(ParseError)` - Invalid line content
+///
+/// # Examples
+/// ```
+/// use battery_joltage::parse_input_str_multiline;
+///
+/// let banks = parse_input_str_multiline("98\n76\n\n5\n").unwrap();
+/// assert_eq!(banks.len(), 2);
+/// assert_eq!(banks[0].batteries, vec![9, 8, 7, 6]);
+/// ```
+pub fn parse_input_str_multiline(content: &str) -> Result<Vec<BatteryBank>, ParseError> {
+    let mut banks = Vec::new();
+    let mut current: Option<Vec<u8>> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+
+        // A blank line closes the bank being built
+        if line.trim().is_empty() {
+            if let Some(batteries) = current.take() {
+                banks.push(BatteryBank { batteries });
+            }
+            continue;
+        }
+
+        match BatteryBank::from_line(line) {
+            Ok(Some(bank)) => {
+                current.get_or_insert_with(Vec::new).extend(bank.batteries);
+            }
+            Ok(None) => {
+                // Comment line - skip it without ending the bank
+                continue;
+            }
+            Err(ParseError::InvalidCharacter { character, .. }) => {
+                return Err(ParseError::InvalidCharacter {
+                    line: line_number,
+                    character,
+                });
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // Don't forget the last bank
+    if let Some(batteries) = current {
+        banks.push(BatteryBank { batteries });
+    }
+
+    Ok(banks)
+}
+
+/// Parse battery banks from a string that may start with a `#n=<count>` header
+///
+/// If the first line is a header such as `#n=12`, its count is returned
+/// alongside the banks. Any other line starting with `#` is a comment and is
+/// skipped like an empty line. Counts too large for a `u64` joltage are not
+/// rejected here; they are reported as an overflow for each bank instead.
+///
+/// # Arguments
+/// * `content` - The input text, optionally starting with a header line
+///
+/// # Returns
+/// * `Ok((Option<usize>, Vec<BatteryBank>))` - The header count, if present, and the banks
+/// * `Err(ParseError)` - Malformed header or invalid line content
+///
+/// # Examples
+/// ```
+/// use battery_joltage::parse_input_str_with_header;
+///
+/// let (n, banks) = parse_input_str_with_header("#n=12\n# comment\n987\n").unwrap();
+/// assert_eq!(n, Some(12));
+/// assert_eq!(banks.len(), 1);
+/// ```
+pub fn parse_input_str_with_header(content: &str) -> Result<(Option<usize>, Vec<BatteryBank>), ParseError> {
+    let header = match content.lines().next().map(str::trim) {
+        Some(line) => match line.strip_prefix("#n=") {
+            Some(value) => match value.trim().parse::<usize>() {
+                Ok(n) if n > 0 => Some(n),
+                _ => return Err(ParseError::InvalidHeader(line.to_string())),
+            },
+            None => None,
+        },
+        None => None,
+    };
+
+    // The header itself is a comment as far as the bank parser is concerned
+    let banks = parse_input_str(content)?;
+
+    Ok((header, banks))
+}
+
+/// Parse an input file that may start with a `#n=<count>` header
+///
+/// Reads the file like `parse_input_file` and then behaves like
+/// `parse_input_str_with_header`.
+///
+/// # Arguments
+/// * `path` - Path to the input file
+///
+/// # Returns
+/// * `Ok((Option<usize>, Vec<BatteryBank>))` - The header count, if present, and the banks
+/// * `Err(ParseError)` - File not found, I/O error, malformed header or invalid line content
+pub fn parse_input_file_with_header(path: &Path) -> Result<(Option<usize>, Vec<BatteryBank>), ParseError> {
+    let content = read_input_file(path)?;
+
+    parse_input_str_with_header(&content)
+}
+
+/// Parse an input file containing battery banks, skipping invalid lines
+///
+/// Behaves like `parse_input_file`, but a line with an invalid character does
+/// not abort parsing. Instead the line is skipped and its error, with the
+/// correct line number, is collected alongside the successfully parsed banks.
+/// If the file cannot be opened or a read fails, that error is recorded and
+/// the banks parsed so far are returned.
+///
+/// # Arguments
+/// * `path` - Path to the input file
+///
+/// # Returns
+/// * `(Vec<BatteryBank>, Vec<ParseError>)` - The valid banks and the errors for every rejected line
+///
+/// # Examples
+/// ```no_run
+/// use battery_joltage::parse_input_file_lenient;
+/// use std::path::Path;
+///
+/// let (banks, errors) = parse_input_file_lenient(Path::new("input.txt"));
+/// println!("Parsed {} battery banks, skipped {} lines", banks.len(), errors.len());
+/// ```
+pub fn parse_input_file_lenient(path: &Path) -> (Vec<BatteryBank>, Vec<ParseError>) {
+    let mut banks = Vec::new();
+    let mut errors = Vec::new();
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            errors.push(ParseError::FileNotFound(path.to_path_buf()));
+            return (banks, errors);
+        }
+        Err(err) => {
+            errors.push(ParseError::IoError(err));
+            return (banks, errors);
+        }
+    };
+
+    let reader = BufReader::new(file);
+
+    for (index, line_result) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = match line_result {
+            Ok(line) => line,
+            Err(err) => {
+                // A read failure means the rest of the file is unavailable
+                errors.push(ParseError::IoError(err));
+                break;
+            }
+        };
+
+        match BatteryBank::from_line(&line) {
+            Ok(Some(bank)) => banks.push(bank),
+            Ok(None) => continue,
+            Err(ParseError::InvalidCharacter { character, .. }) => {
+                // Record the error with the correct line number and keep going
+                errors.push(ParseError::InvalidCharacter {
+                    line: line_number,
+                    character,
+                });
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    (banks, errors)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -567,6 +1418,39 @@ mod tests {
         assert_eq!(bank.batteries, vec![0, 0, 0, 0]);
     }
 
+    // Tests for BatteryBank::from_digits()
+
+    #[test]
+    fn from_digits_valid() {
+        let bank = BatteryBank::from_digits(vec![0, 5, 9]).unwrap();
+        assert_eq!(bank.batteries, vec![0, 5, 9]);
+    }
+
+    #[test]
+    fn from_digits_empty() {
+        let bank = BatteryBank::from_digits(Vec::new()).unwrap();
+        assert!(bank.batteries.is_empty());
+    }
+
+    #[test]
+    fn from_digits_invalid_value() {
+        let result = BatteryBank::from_digits([1, 2, 12, 3]);
+        match result.unwrap_err() {
+            ParseError::InvalidDigit { index, value } => {
+                assert_eq!(index, 2);
+                assert_eq!(value, 12);
+            }
+            _ => panic!("Expected InvalidDigit error"),
+        }
+    }
+
+    #[test]
+    fn from_digits_matches_from_line() {
+        let from_line = BatteryBank::from_line("8675309").unwrap().unwrap();
+        let from_digits = BatteryBank::from_digits([8, 6, 7, 5, 3, 0, 9]).unwrap();
+        assert_eq!(from_line, from_digits);
+    }
+
     // Tests for BatteryBank::find_max_joltage()
 
     #[test]
@@ -621,6 +1505,7 @@ mod tests {
                 assert_eq!(count, 0);
                 assert_eq!(required, 2);
             }
+            _ => panic!("Expected InsufficientBatteries error"),
         }
     }
 
@@ -636,6 +1521,7 @@ mod tests {
                 assert_eq!(count, 1);
                 assert_eq!(required, 2);
             }
+            _ => panic!("Expected InsufficientBatteries error"),
         }
     }
 
@@ -697,52 +1583,300 @@ mod tests {
         assert!(result.is_ok());
         let banks = result.unwrap();
         assert_eq!(banks.len(), 3);
-        assert_eq!(banks[0].batteries, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
-        assert_eq!(banks[1].batteries, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
-        assert_eq!(banks[2].batteries, vec![1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(banks[0].batteries, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(banks[1].batteries, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(banks[2].batteries, vec![1, 1, 1, 1, 1, 1, 1, 1, 1]);
+
+        // Clean up
+        fs::remove_file(temp_path).unwrap();
+    }
+
+    #[test]
+    fn parse_input_file_with_empty_lines() {
+        // Create a temporary file with empty lines mixed in
+        let temp_path = "test_empty_lines.txt";
+        let content = "123\n\n456\n   \n789\n";
+        fs::write(temp_path, content).unwrap();
+
+        let result = parse_input_file(Path::new(temp_path));
+        assert!(result.is_ok());
+        let banks = result.unwrap();
+        assert_eq!(banks.len(), 3);
+        assert_eq!(banks[0].batteries, vec![1, 2, 3]);
+        assert_eq!(banks[1].batteries, vec![4, 5, 6]);
+        assert_eq!(banks[2].batteries, vec![7, 8, 9]);
+
+        // Clean up
+        fs::remove_file(temp_path).unwrap();
+    }
+
+    #[test]
+    fn parse_input_file_invalid_character() {
+        // Create a temporary file with an invalid character
+        let temp_path = "test_invalid.txt";
+        let content = "123\n456x789\n999\n";
+        fs::write(temp_path, content).unwrap();
+
+        let result = parse_input_file(Path::new(temp_path));
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            ParseError::InvalidCharacter { line, character } => {
+                assert_eq!(line, 2);
+                assert_eq!(character, 'x');
+            }
+            _ => panic!("Expected InvalidCharacter error"),
+        }
+
+        // Clean up
+        fs::remove_file(temp_path).unwrap();
+    }
+
+    // Tests for parse_input_str()
+
+    #[test]
+    fn parse_input_str_with_empty_lines() {
+        let banks = parse_input_str("123\n\n456\n   \n789\n").unwrap();
+        assert_eq!(banks.len(), 3);
+        assert_eq!(banks[0].batteries, vec![1, 2, 3]);
+        assert_eq!(banks[1].batteries, vec![4, 5, 6]);
+        assert_eq!(banks[2].batteries, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn parse_input_str_empty() {
+        assert!(parse_input_str("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_input_str_invalid_character() {
+        match parse_input_str("123\n\n456x789\n").unwrap_err() {
+            ParseError::InvalidCharacter { line, character } => {
+                assert_eq!(line, 3);
+                assert_eq!(character, 'x');
+            }
+            _ => panic!("Expected InvalidCharacter error"),
+        }
+    }
+
+    #[test]
+    fn parse_input_file_lenient_skips_invalid_lines() {
+        let temp_path = "test_lenient.txt";
+        let content = "123\n456x789\n\n999\n12?3\n";
+        fs::write(temp_path, content).unwrap();
+
+        let (banks, errors) = parse_input_file_lenient(Path::new(temp_path));
+        assert_eq!(banks.len(), 2);
+        assert_eq!(banks[0].batteries, vec![1, 2, 3]);
+        assert_eq!(banks[1].batteries, vec![9, 9, 9]);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidCharacter { line, character } => {
+                assert_eq!(*line, 2);
+                assert_eq!(*character, 'x');
+            }
+            _ => panic!("Expected InvalidCharacter error"),
+        }
+        match &errors[1] {
+            ParseError::InvalidCharacter { line, character } => {
+                assert_eq!(*line, 5);
+                assert_eq!(*character, '?');
+            }
+            _ => panic!("Expected InvalidCharacter error"),
+        }
+
+        // Clean up
+        fs::remove_file(temp_path).unwrap();
+    }
+
+    #[test]
+    fn parse_input_file_lenient_not_found() {
+        let (banks, errors) = parse_input_file_lenient(Path::new("nonexistent_lenient.txt"));
+        assert!(banks.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::FileNotFound(_)));
+    }
+
+    // Tests for calculate_total_joltage_n_skip_zero()
+
+    #[test]
+    fn skip_zero_omits_all_zero_banks() {
+        let banks = vec![
+            BatteryBank {
+                batteries: vec![0, 0, 0],
+            },
+            BatteryBank {
+                batteries: vec![0, 5, 0],
+            },
+            BatteryBank {
+                batteries: vec![0],
+            },
+            BatteryBank {
+                batteries: vec![9, 8, 7],
+            },
+        ];
+        let result = calculate_total_joltage_n_skip_zero(&banks, 2);
+
+        let indices: Vec<usize> = result.bank_results.iter().map(|r| r.bank_index).collect();
+        assert_eq!(indices, vec![1, 3]);
+        assert_eq!(result.total_joltage, 50 + 98);
+        assert_eq!(result.total_joltage, calculate_total_joltage_n(&banks, 2).total_joltage);
+        // The too-short [0] bank is still reported even though it is all-zero
+        assert_eq!(result.error_count(), 1);
+    }
+
+    #[test]
+    fn skip_zero_keeps_errors_for_other_banks() {
+        let banks = vec![
+            BatteryBank {
+                batteries: vec![],
+            },
+            BatteryBank {
+                batteries: vec![7],
+            },
+        ];
+        let result = calculate_total_joltage_n_skip_zero(&banks, 2);
+
+        assert_eq!(result.success_count(), 0);
+        assert_eq!(result.error_count(), 2);
+    }
+
+    // Tests for parse_and_total_files()
+
+    #[test]
+    fn parse_and_total_files_uses_global_indices() {
+        let first = "test_batch_first.txt";
+        let second = "test_batch_second.txt";
+        fs::write(first, "987\n12\n").unwrap();
+        fs::write(second, "5\n811\n").unwrap();
+
+        let result = parse_and_total_files(&[first, second], 2).unwrap();
+
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+
+        let indices: Vec<usize> = result.bank_results.iter().map(|r| r.bank_index).collect();
+        assert_eq!(indices, vec![0, 1, 3]);
+        assert_eq!(result.total_joltage, 98 + 12 + 81);
+        match &result.errors[0] {
+            ProcessingError::JoltageError { bank_index, .. } => assert_eq!(*bank_index, 2),
+            _ => panic!("Expected JoltageError"),
+        }
+    }
+
+    #[test]
+    fn parse_and_total_files_reports_offending_path() {
+        let good = "test_batch_good.txt";
+        let bad = "test_batch_bad.txt";
+        fs::write(good, "987\n").unwrap();
+        fs::write(bad, "98x\n").unwrap();
+
+        let result = parse_and_total_files(&[good, bad], 2);
+
+        fs::remove_file(good).unwrap();
+        fs::remove_file(bad).unwrap();
+
+        match result {
+            Err(ProcessingError::FileError { path, error: ParseError::InvalidCharacter { line, character } }) => {
+                assert_eq!(path, PathBuf::from(bad));
+                assert_eq!(line, 1);
+                assert_eq!(character, 'x');
+            }
+            other => panic!("Expected FileError, got {:?}", other),
+        }
+    }
+
+    // Tests for parse_input_str_multiline()
+
+    #[test]
+    fn parse_input_str_multiline_joins_wrapped_lines() {
+        let content = "987\n654\n\n\n321\n\n12\n# note\n34\n";
+        let banks = parse_input_str_multiline(content).unwrap();
+
+        assert_eq!(banks.len(), 3);
+        assert_eq!(banks[0].batteries, vec![9, 8, 7, 6, 5, 4]);
+        assert_eq!(banks[1].batteries, vec![3, 2, 1]);
+        assert_eq!(banks[2].batteries, vec![1, 2, 3, 4]);
+    }
 
-        // Clean up
-        fs::remove_file(temp_path).unwrap();
+    #[test]
+    fn parse_input_str_multiline_reports_offending_line() {
+        let content = "987\n654\n\n321\n3x1\n";
+        match parse_input_str_multiline(content) {
+            Err(ParseError::InvalidCharacter { line, character }) => {
+                assert_eq!(line, 5);
+                assert_eq!(character, 'x');
+            }
+            other => panic!("Expected InvalidCharacter, got {:?}", other),
+        }
     }
 
     #[test]
-    fn parse_input_file_with_empty_lines() {
-        // Create a temporary file with empty lines mixed in
-        let temp_path = "test_empty_lines.txt";
-        let content = "123\n\n456\n   \n789\n";
-        fs::write(temp_path, content).unwrap();
+    fn parse_input_file_multiline_reads_file() {
+        let temp_path = "test_multiline.txt";
+        fs::write(temp_path, "12\n34\n\n56\n").unwrap();
 
-        let result = parse_input_file(Path::new(temp_path));
-        assert!(result.is_ok());
-        let banks = result.unwrap();
-        assert_eq!(banks.len(), 3);
-        assert_eq!(banks[0].batteries, vec![1, 2, 3]);
-        assert_eq!(banks[1].batteries, vec![4, 5, 6]);
-        assert_eq!(banks[2].batteries, vec![7, 8, 9]);
+        let result = parse_input_file_multiline(Path::new(temp_path));
 
-        // Clean up
         fs::remove_file(temp_path).unwrap();
+
+        let banks = result.unwrap();
+        assert_eq!(banks.len(), 2);
+        assert_eq!(banks[0].batteries, vec![1, 2, 3, 4]);
     }
 
+    // Tests for parse_input_str_with_header()
+
     #[test]
-    fn parse_input_file_invalid_character() {
-        // Create a temporary file with an invalid character
-        let temp_path = "test_invalid.txt";
-        let content = "123\n456x789\n999\n";
-        fs::write(temp_path, content).unwrap();
+    fn parse_input_str_with_header_reads_count() {
+        let (n, banks) = parse_input_str_with_header("#n=12\n987\n123\n").unwrap();
+        assert_eq!(n, Some(12));
+        assert_eq!(banks.len(), 2);
+    }
 
-        let result = parse_input_file(Path::new(temp_path));
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            ParseError::InvalidCharacter { line, character } => {
-                assert_eq!(line, 2);
-                assert_eq!(character, 'x');
+    #[test]
+    fn parse_input_str_with_header_missing_header() {
+        let (n, banks) = parse_input_str_with_header("987\n# not a header\n123\n").unwrap();
+        assert_eq!(n, None);
+        assert_eq!(banks.len(), 2);
+
+        let (n, banks) = parse_input_str_with_header("").unwrap();
+        assert_eq!(n, None);
+        assert!(banks.is_empty());
+    }
+
+    #[test]
+    fn parse_input_str_with_header_only_first_line_counts() {
+        let (n, _) = parse_input_str_with_header("987\n#n=12\n").unwrap();
+        assert_eq!(n, None);
+    }
+
+    #[test]
+    fn parse_input_str_with_header_rejects_bad_count() {
+        for content in ["#n=abc\n987", "#n=0\n987", "#n=\n987"] {
+            match parse_input_str_with_header(content) {
+                Err(ParseError::InvalidHeader(_)) => {}
+                other => panic!("Expected InvalidHeader for {:?}, got {:?}", content, other),
             }
-            _ => panic!("Expected InvalidCharacter error"),
         }
+    }
 
-        // Clean up
-        fs::remove_file(temp_path).unwrap();
+    #[test]
+    fn parse_input_str_with_header_large_count_overflows_per_bank() {
+        let content = "#n=25\n9876543219876543219876543219876\n";
+        let (n, banks) = parse_input_str_with_header(content).unwrap();
+        assert_eq!(n, Some(25));
+
+        let result = calculate_total_joltage_n(&banks, 25);
+        assert!(result.bank_results.is_empty());
+        assert_eq!(result.error_count(), 1);
+        match &result.errors[0] {
+            ProcessingError::JoltageError {
+                error: JoltageError::Overflow { required: 25 },
+                ..
+            } => {}
+            other => panic!("Expected Overflow error, got {:?}", other),
+        }
     }
 
     // Tests for calculate_total_joltage()
@@ -926,6 +2060,7 @@ mod tests {
                 assert_eq!(count, 3);
                 assert_eq!(required, 5);
             }
+            _ => panic!("Expected InsufficientBatteries error"),
         }
     }
 
@@ -937,4 +2072,389 @@ mod tests {
         let result = bank.find_max_joltage_n(1);
         assert!(result.is_err());
     }
+
+    // Tests for BatteryBank::find_max_joltage_n_leading()
+
+    #[test]
+    fn find_max_joltage_n_leading_zero_selected() {
+        let bank = BatteryBank {
+            batteries: vec![0, 0, 5],
+        };
+        assert_eq!(bank.find_max_joltage_n_leading(2).unwrap(), (5, true));
+        assert_eq!(bank.find_max_joltage_n(2).unwrap(), 5);
+    }
+
+    #[test]
+    fn find_max_joltage_n_leading_no_zero() {
+        let bank = BatteryBank {
+            batteries: vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1],
+        };
+        assert_eq!(bank.find_max_joltage_n_leading(12).unwrap(), (987654321111, false));
+    }
+
+    #[test]
+    fn find_max_joltage_n_leading_zero_batteries() {
+        let bank = BatteryBank {
+            batteries: vec![0, 1],
+        };
+        assert_eq!(bank.find_max_joltage_n_leading(0).unwrap(), (0, false));
+    }
+
+    #[test]
+    fn find_max_joltage_n_overflow_is_an_error() {
+        let bank = BatteryBank::from_line("9876543211111111111111111111111").unwrap().unwrap();
+
+        assert!(matches!(
+            bank.find_max_joltage_n(21),
+            Err(JoltageError::Overflow { required: 21 })
+        ));
+        assert!(matches!(
+            bank.find_max_joltage_n_leading(21),
+            Err(JoltageError::Overflow { required: 21 })
+        ));
+
+        // Twenty digits still fit when the value is at most u64::MAX
+        let bank = BatteryBank::from_line("11111111111111111111").unwrap().unwrap();
+        assert_eq!(bank.find_max_joltage_n(20).unwrap(), 11_111_111_111_111_111_111);
+    }
+
+    #[test]
+    fn find_max_joltage_n_leading_insufficient_batteries() {
+        let bank = BatteryBank {
+            batteries: vec![0],
+        };
+        assert!(bank.find_max_joltage_n_leading(2).is_err());
+    }
+
+    // Tests for BatteryBank::find_max_product_joltage()
+
+    #[test]
+    fn find_max_product_joltage_picks_largest() {
+        let bank = BatteryBank {
+            batteries: vec![3, 1, 9, 4, 7],
+        };
+        assert_eq!(bank.find_max_product_joltage(3).unwrap(), 9 * 7 * 4);
+    }
+
+    #[test]
+    fn find_max_product_joltage_avoids_zero_when_possible() {
+        let bank = BatteryBank {
+            batteries: vec![0, 2, 0, 3],
+        };
+        assert_eq!(bank.find_max_product_joltage(2).unwrap(), 6);
+        assert_eq!(bank.find_max_product_joltage(3).unwrap(), 0);
+    }
+
+    #[test]
+    fn find_max_product_joltage_zero_batteries() {
+        let bank = BatteryBank {
+            batteries: vec![5],
+        };
+        assert_eq!(bank.find_max_product_joltage(0).unwrap(), 1);
+    }
+
+    #[test]
+    fn find_max_product_joltage_overflow() {
+        // 9^20 fits in a u64, 9^21 does not
+        let bank = BatteryBank {
+            batteries: vec![9; 21],
+        };
+        assert_eq!(bank.find_max_product_joltage(20).unwrap(), 9u64.pow(20));
+        match bank.find_max_product_joltage(21).unwrap_err() {
+            JoltageError::Overflow { required } => assert_eq!(required, 21),
+            _ => panic!("Expected Overflow error"),
+        }
+    }
+
+    #[test]
+    fn find_max_product_joltage_insufficient_batteries() {
+        let bank = BatteryBank {
+            batteries: vec![1, 2],
+        };
+        assert!(bank.find_max_product_joltage(3).is_err());
+    }
+
+    // Tests for ProcessingResult::to_csv()
+
+    #[test]
+    fn to_csv_skips_errored_banks() {
+        let banks = vec![
+            BatteryBank {
+                batteries: vec![9, 8, 7],
+            },
+            BatteryBank {
+                batteries: vec![5],
+            },
+            BatteryBank {
+                batteries: vec![1, 2, 3],
+            },
+        ];
+        let result = calculate_total_joltage(&banks);
+
+        assert_eq!(
+            result.to_csv(),
+            "bank_index,max_joltage\n1,98\n3,23\nerrors,1\ntotal,121\n"
+        );
+    }
+
+    #[test]
+    fn to_csv_empty_result() {
+        let result = calculate_total_joltage(&[]);
+        assert_eq!(result.to_csv(), "bank_index,max_joltage\nerrors,0\ntotal,0\n");
+    }
+
+    // Tests for ProcessingResult counts
+
+    #[test]
+    fn processing_result_counts() {
+        let banks = vec![
+            BatteryBank {
+                batteries: vec![9, 8, 7],
+            },
+            BatteryBank {
+                batteries: vec![],
+            },
+            BatteryBank {
+                batteries: vec![1, 2],
+            },
+            BatteryBank {
+                batteries: vec![4],
+            },
+        ];
+        let result = calculate_total_joltage(&banks);
+
+        assert_eq!(result.success_count(), 2);
+        assert_eq!(result.error_count(), 2);
+        assert_eq!(result.total_count(), banks.len());
+        assert_eq!(result.success_rate(), 0.5);
+    }
+
+    #[test]
+    fn processing_result_success_rate_empty() {
+        let result = calculate_total_joltage(&[]);
+        assert_eq!(result.total_count(), 0);
+        assert_eq!(result.success_rate(), 0.0);
+    }
+
+    // Tests for ProcessingResult::top_k()
+
+    #[test]
+    fn top_k_orders_by_joltage_then_index() {
+        let banks = vec![
+            BatteryBank {
+                batteries: vec![5, 4],
+            },
+            BatteryBank {
+                batteries: vec![9, 8],
+            },
+            BatteryBank {
+                batteries: vec![1],
+            },
+            BatteryBank {
+                batteries: vec![5, 4],
+            },
+        ];
+        let result = calculate_total_joltage(&banks);
+
+        let top: Vec<(usize, u64)> = result.top_k(3).iter()
+            .map(|r| (r.bank_index, r.max_joltage))
+            .collect();
+        assert_eq!(top, vec![(1, 98), (0, 54), (3, 54)]);
+
+        // k larger than the number of successful banks returns them all
+        assert_eq!(result.top_k(10).len(), 3);
+        assert!(result.top_k(0).is_empty());
+        assert_eq!(result.bank_results[0].bank_index, 0);
+    }
+
+    // Tests for BatteryBank::all_pair_joltages()
+
+    #[test]
+    fn all_pair_joltages_max_matches_find_max_joltage() {
+        let bank = BatteryBank {
+            batteries: vec![8, 1, 8, 1, 9, 1, 2],
+        };
+        let joltages = bank.all_pair_joltages();
+        assert_eq!(joltages.len(), 7 * 6 / 2);
+        assert_eq!(joltages.iter().max().copied(), Some(bank.find_max_joltage().unwrap()));
+    }
+
+    #[test]
+    fn all_pair_joltages_too_few_batteries() {
+        let bank = BatteryBank {
+            batteries: vec![7],
+        };
+        assert!(bank.all_pair_joltages().is_empty());
+    }
+
+    // Tests for BatteryBank::max_joltage_n_is_unique()
+
+    #[test]
+    fn max_joltage_n_is_unique_harmless_tie() {
+        // The tie between the two 9s cannot be completed to 99 from index 1
+        let bank = BatteryBank {
+            batteries: vec![9, 9, 1],
+        };
+        assert!(bank.max_joltage_n_is_unique(2).unwrap());
+    }
+
+    #[test]
+    fn max_joltage_n_is_unique_ambiguous_selection() {
+        // 9119 -> 919 can use either of the 1s
+        let bank = BatteryBank {
+            batteries: vec![9, 1, 1, 9],
+        };
+        assert!(!bank.max_joltage_n_is_unique(3).unwrap());
+
+        let bank = BatteryBank {
+            batteries: vec![5, 5],
+        };
+        assert!(!bank.max_joltage_n_is_unique(1).unwrap());
+    }
+
+    #[test]
+    fn max_joltage_n_is_unique_exact_length_and_zero() {
+        let bank = BatteryBank {
+            batteries: vec![3, 3, 3],
+        };
+        assert!(bank.max_joltage_n_is_unique(3).unwrap());
+        assert!(bank.max_joltage_n_is_unique(0).unwrap());
+        assert!(bank.max_joltage_n_is_unique(4).is_err());
+    }
+
+    // Tests for BatteryBank::can_select()
+
+    #[test]
+    fn can_select_matches_insufficient_batteries_error() {
+        let bank = BatteryBank {
+            batteries: vec![4, 2, 7],
+        };
+        for n in 0..=5 {
+            assert_eq!(bank.can_select(n), bank.find_max_joltage_n(n).is_ok());
+        }
+        assert!(BatteryBank { batteries: vec![] }.can_select(0));
+    }
+
+    // Tests for BatteryBank::digit_counts()
+
+    #[test]
+    fn digit_counts_histogram_sums_to_length() {
+        let bank = BatteryBank::from_line("987654321111111").unwrap().unwrap();
+        let counts = bank.digit_counts();
+
+        assert_eq!(counts, [0, 7, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(counts.iter().sum::<usize>(), bank.batteries.len());
+        assert_eq!(BatteryBank { batteries: vec![] }.digit_counts(), [0; 10]);
+    }
+
+    // Tests for BatteryBank::max_contiguous_joltage_n()
+
+    #[test]
+    fn max_contiguous_joltage_n_differs_from_subsequence_when_gaps_help() {
+        let bank = BatteryBank::from_line("818181911112111").unwrap().unwrap();
+
+        assert_eq!(bank.max_contiguous_joltage_n(2).unwrap(), 91);
+        assert_eq!(bank.find_max_joltage_n(2).unwrap(), 92);
+        assert_eq!(bank.max_contiguous_joltage_n(12).unwrap(), 818191111211);
+        assert!(bank.max_contiguous_joltage_n(12).unwrap() < bank.find_max_joltage_n(12).unwrap());
+    }
+
+    #[test]
+    fn max_contiguous_joltage_n_matches_subsequence_for_sorted_bank() {
+        let bank = BatteryBank::from_line("987654321111111").unwrap().unwrap();
+
+        for n in 0..=bank.batteries.len() {
+            assert_eq!(bank.max_contiguous_joltage_n(n).unwrap(), bank.find_max_joltage_n(n).unwrap());
+        }
+    }
+
+    #[test]
+    fn max_contiguous_joltage_n_errors() {
+        let bank = BatteryBank { batteries: vec![1, 2] };
+        assert!(matches!(
+            bank.max_contiguous_joltage_n(3),
+            Err(JoltageError::InsufficientBatteries { count: 2, required: 3 })
+        ));
+
+        let bank = BatteryBank { batteries: vec![9; 21] };
+        assert!(matches!(
+            bank.max_contiguous_joltage_n(21),
+            Err(JoltageError::Overflow { required: 21 })
+        ));
+    }
+
+    // Tests for calculate_total_joltage_n_timed()
+
+    #[test]
+    fn calculate_total_joltage_n_timed_matches_untimed() {
+        let banks = vec![
+            BatteryBank { batteries: vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1] },
+            BatteryBank { batteries: vec![1, 2] },
+            BatteryBank { batteries: vec![8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1] },
+        ];
+
+        for n in [2, 12] {
+            let expected = calculate_total_joltage_n(&banks, n);
+            let (result, timings) = calculate_total_joltage_n_timed(&banks, n);
+
+            assert_eq!(result.to_csv(), expected.to_csv());
+            assert_eq!(timings.len(), banks.len());
+        }
+    }
+
+    // Tests for BatteryBank::reversed()
+
+    #[test]
+    fn reversed_mirrors_bank() {
+        let bank = BatteryBank::from_line("811111111111119").unwrap().unwrap();
+
+        assert_eq!(bank.reversed().batteries, vec![9, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 8]);
+        assert_eq!(bank.reversed().reversed(), bank);
+    }
+
+    #[test]
+    fn reversed_keeps_max_joltage_for_one_battery_and_palindromes() {
+        for line in ["987654321111111", "811111111111119", "234234234234278"] {
+            let bank = BatteryBank::from_line(line).unwrap().unwrap();
+            assert_eq!(bank.reversed().find_max_joltage_n(1).unwrap(), bank.find_max_joltage_n(1).unwrap());
+        }
+
+        let palindrome = BatteryBank::from_line("1293921").unwrap().unwrap();
+        assert_eq!(palindrome.reversed(), palindrome);
+    }
+
+    #[test]
+    fn reversed_can_change_max_joltage() {
+        let bank = BatteryBank { batteries: vec![1, 9] };
+
+        assert_eq!(bank.find_max_joltage_n(2).unwrap(), 19);
+        assert_eq!(bank.reversed().find_max_joltage_n(2).unwrap(), 91);
+    }
+
+    // Tests for max_bank_joltage_n()
+
+    #[test]
+    fn max_bank_joltage_n_prefers_earliest_on_ties() {
+        let banks = vec![
+            BatteryBank { batteries: vec![1] },
+            BatteryBank { batteries: vec![8, 1, 9] },
+            BatteryBank { batteries: vec![2, 8, 9] },
+            BatteryBank { batteries: vec![7, 7] },
+        ];
+
+        let best = max_bank_joltage_n(&banks, 2).unwrap();
+        assert_eq!(best.bank_index, 1);
+        assert_eq!(best.max_joltage, 89);
+
+        let expected = calculate_total_joltage_n(&banks, 2);
+        assert_eq!(expected.top_k(1)[0].bank_index, best.bank_index);
+    }
+
+    #[test]
+    fn max_bank_joltage_n_none_when_every_bank_errors() {
+        let banks = vec![BatteryBank { batteries: vec![1] }, BatteryBank { batteries: vec![] }];
+
+        assert!(max_bank_joltage_n(&banks, 2).is_none());
+        assert!(max_bank_joltage_n(&[], 2).is_none());
+    }
 }
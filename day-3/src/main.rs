@@ -1,7 +1,7 @@
 // Battery Joltage Calculator
 // Main entry point for the application
 
-use battery_joltage::{parse_input_file, calculate_total_joltage_n};
+use battery_joltage::{parse_input_file_with_header, calculate_total_joltage_n};
 use std::env;
 use std::path::Path;
 use std::process;
@@ -11,7 +11,7 @@ fn main() {
     let args: Vec<String> = env::args().collect();
 
     let mut input_path = "input.txt";
-    let mut n_batteries = 2; // Default to 2 for backward compatibility
+    let mut cli_batteries = None;
 
     let mut i = 1;
     while i < args.len() {
@@ -26,7 +26,7 @@ fn main() {
 
                 match args[i + 1].parse::<usize>() {
                     Ok(n) if n > 0 => {
-                        n_batteries = n;
+                        cli_batteries = Some(n);
                         i += 2;
                     }
                     Ok(_) => {
@@ -53,15 +53,18 @@ fn main() {
         }
     }
 
-    // Parse the input file
-    let banks = match parse_input_file(Path::new(input_path)) {
-        Ok(banks) => banks,
+    // Parse the input file, including an optional #n=<count> header
+    let (header_batteries, banks) = match parse_input_file_with_header(Path::new(input_path)) {
+        Ok(parsed) => parsed,
         Err(err) => {
             eprintln!("Error parsing input file: {}", err);
             process::exit(1);
         }
     };
 
+    // The CLI flag overrides the file header, which overrides the default of 2
+    let n_batteries = cli_batteries.or(header_batteries).unwrap_or(2);
+
     // Calculate total joltage across all banks using n batteries
     let result = calculate_total_joltage_n(&banks, n_batteries);
 
@@ -81,7 +84,7 @@ fn main() {
     }
 
     // Display errors if any occurred
-    if !result.errors.is_empty() {
+    if result.error_count() > 0 {
         println!("Errors encountered:");
         for error in &result.errors {
             eprintln!("  {}", error);
@@ -92,10 +95,14 @@ fn main() {
     // Display total output joltage prominently
     println!("==========================");
     println!("TOTAL OUTPUT JOLTAGE: {}", result.total_joltage);
+    println!("Banks processed: {} of {} ({:.1}% success)",
+             result.success_count(),
+             result.total_count(),
+             result.success_rate() * 100.0);
     println!("==========================");
 
     // Set exit code: 0 for success, non-zero if there were errors
-    if !result.errors.is_empty() {
+    if result.error_count() > 0 {
         process::exit(2);
     }
 }
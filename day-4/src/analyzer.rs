@@ -1,6 +1,8 @@
 // Analyzer module for counting accessible paper rolls
 
 use crate::grid::Grid;
+use std::collections::HashMap;
+use std::thread;
 
 /// Counts the total number of accessible paper rolls in the grid
 /// Iterates through all grid positions and counts positions that are both paper rolls and accessible
@@ -21,6 +23,43 @@ pub fn count_accessible_rolls(grid: &Grid) -> usize {
     count
 }
 
+/// Counts the total number of accessible paper rolls using one thread per CPU
+/// Rows are split into contiguous bands, each counted on its own thread, and the partial counts are summed
+/// The grid is only read during this pass, so the result always equals count_accessible_rolls
+pub fn count_accessible_rolls_parallel(grid: &Grid) -> usize {
+    if grid.rows() == 0 {
+        return 0;
+    }
+
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let rows_per_worker = grid.rows().div_ceil(workers);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..grid.rows())
+            .step_by(rows_per_worker)
+            .map(|first_row| {
+                let last_row = (first_row + rows_per_worker).min(grid.rows());
+                scope.spawn(move || {
+                    let mut count = 0;
+                    for row in first_row..last_row {
+                        for col in 0..grid.cols() {
+                            if grid.is_accessible(row, col) {
+                                count += 1;
+                            }
+                        }
+                    }
+                    count
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("worker thread panicked"))
+            .sum()
+    })
+}
+
 /// Performs iterative removal simulation and returns total count of removed rolls
 /// Repeatedly finds and removes all accessible rolls until none remain
 /// Requirements: 5.1, 5.2, 5.4, 5.5, 5.6
@@ -28,21 +67,136 @@ pub fn count_removable_rolls(grid: &mut Grid) -> usize {
     let mut total_removed = 0;
 
     loop {
-        // Find all currently accessible roll positions
-        let accessible_rolls = grid.find_accessible_rolls();
+        // Remove one wave of accessible rolls
+        let removed = remove_accessible_once(grid);
 
         // If no accessible rolls remain, terminate the process
+        if removed == 0 {
+            break;
+        }
+
+        // Increment the total count by the number of rolls removed in this iteration
+        total_removed += removed;
+    }
+
+    total_removed
+}
+
+/// Runs the full removal simulation and records how many rolls each wave removed
+/// Index i holds the number of rolls removed in wave i; waves that remove nothing are not recorded
+/// The sum of the counts equals count_removable_rolls for the same grid
+pub fn removal_counts_per_iteration(grid: &mut Grid) -> Vec<usize> {
+    let mut counts = Vec::new();
+
+    loop {
+        let removed = remove_accessible_once(grid);
+        if removed == 0 {
+            break;
+        }
+        counts.push(removed);
+    }
+
+    counts
+}
+
+/// Runs the full removal simulation and records the wave in which each roll was removed
+/// Maps each removed position (row, col) to the 0-based iteration that removed it
+/// Stuck rolls are never removed, so they are absent from the map
+pub fn removal_depth_map(grid: &mut Grid) -> HashMap<(usize, usize), usize> {
+    let mut depths = HashMap::new();
+
+    for depth in 0.. {
+        let accessible_rolls = grid.find_accessible_rolls();
         if accessible_rolls.is_empty() {
             break;
         }
 
-        // Remove all accessible rolls simultaneously
-        for (row, col) in accessible_rolls.iter() {
-            grid.remove_roll(*row, *col);
+        // Remove the whole wave at once, as remove_accessible_once does
+        for (row, col) in accessible_rolls {
+            grid.remove_roll(row, col);
+            depths.insert((row, col), depth);
         }
+    }
 
-        // Increment the total count by the number of rolls removed in this iteration
-        total_removed += accessible_rolls.len();
+    depths
+}
+
+/// Performs a single wave of the removal simulation
+/// Finds all currently accessible rolls, removes them simultaneously and returns how many were removed
+/// Returns 0 once the grid is stable, so callers can step through the simulation one wave at a time
+pub fn remove_accessible_once(grid: &mut Grid) -> usize {
+    // Find all currently accessible roll positions
+    let accessible_rolls = grid.find_accessible_rolls();
+
+    // Remove all accessible rolls simultaneously
+    for (row, col) in accessible_rolls.iter() {
+        let removed = grid.remove_roll(*row, *col);
+        debug_assert!(removed, "accessible position ({}, {}) did not hold a roll", row, col);
+    }
+
+    accessible_rolls.len()
+}
+
+/// Checks a proposed removal order against the accessibility rules
+/// Removes the rolls one at a time in the given order, so each removal sees all earlier ones
+/// Returns Err(i) for the first index whose position was not an accessible roll at that point;
+/// the grid is left with the removals before index i applied
+pub fn validate_removal_order(grid: &mut Grid, order: &[(usize, usize)]) -> Result<(), usize> {
+    for (index, &(row, col)) in order.iter().enumerate() {
+        // is_accessible is false for empty or out-of-bounds positions too
+        if !grid.is_accessible(row, col) {
+            return Err(index);
+        }
+
+        grid.remove_roll(row, col);
+    }
+
+    Ok(())
+}
+
+/// Runs the full removal simulation and returns the rolls that can never be removed
+/// Returns the positions (row, col) of all rolls still present once no more are accessible
+pub fn find_stuck_rolls(grid: &mut Grid) -> Vec<(usize, usize)> {
+    // Remove everything that can be removed
+    count_removable_rolls(grid);
+
+    let mut stuck_positions = Vec::new();
+
+    // Any roll left behind is permanently stuck
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            if grid.is_paper_roll(row, col) {
+                stuck_positions.push((row, col));
+            }
+        }
+    }
+
+    stuck_positions
+}
+
+/// Performs a component-based removal simulation and returns the total count of removed rolls
+/// Each round, every 8-connected component that contains at least one accessible roll is removed entirely
+/// Rounds repeat until no remaining component has an accessible roll
+pub fn count_component_removals(grid: &mut Grid) -> usize {
+    let mut total_removed = 0;
+
+    loop {
+        // Decide which components go before removing any, so the round is simultaneous
+        let doomed: Vec<Vec<(usize, usize)>> = grid
+            .connected_components()
+            .into_iter()
+            .filter(|component| component.iter().any(|&(row, col)| grid.is_accessible(row, col)))
+            .collect();
+
+        if doomed.is_empty() {
+            break;
+        }
+
+        for (row, col) in doomed.into_iter().flatten() {
+            if grid.remove_roll(row, col) {
+                total_removed += 1;
+            }
+        }
     }
 
     total_removed
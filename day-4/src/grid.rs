@@ -1,5 +1,39 @@
 // Grid module for representing the warehouse layout
 
+use std::fmt;
+
+/// The 8 direction offsets for adjacent positions
+/// (row_offset, col_offset)
+const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1), // top-left
+    (-1, 0),  // top
+    (-1, 1),  // top-right
+    (0, -1),  // left
+    (0, 1),   // right
+    (1, -1),  // bottom-left
+    (1, 0),   // bottom
+    (1, 1),   // bottom-right
+];
+
+/// Errors that can occur when building a grid in strict mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A character other than '@' or '.' was found at the given position
+    UnexpectedCharacter { row: usize, col: usize, character: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::UnexpectedCharacter { row, col, character } => {
+                write!(f, "Unexpected character '{}' at row {}, column {}", character, row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
 pub struct Grid {
     cells: Vec<Vec<char>>,
     rows: usize,
@@ -33,6 +67,40 @@ impl Grid {
         Grid { cells, rows, cols }
     }
 
+    /// Constructs a Grid from input string, rejecting characters other than '@' and '.'
+    /// Returns the first unexpected character in row-major order along with its position
+    /// Accepted input produces exactly the same grid as new
+    pub fn new_strict(input: String) -> Result<Grid, GridError> {
+        for (row, line) in input.lines().enumerate() {
+            for (col, character) in line.chars().enumerate() {
+                if character != '@' && character != '.' {
+                    return Err(GridError::UnexpectedCharacter { row, col, character });
+                }
+            }
+        }
+
+        Ok(Grid::new(input))
+    }
+
+    /// Returns a new grid holding the height x width region whose top-left corner is (top, left)
+    /// Positions outside this grid are filled with empty space, so the result is always height x width
+    /// The crop boundary becomes the new grid's edge, so rolls outside the region no longer count as neighbors
+    pub fn crop(&self, top: usize, left: usize, height: usize, width: usize) -> Grid {
+        let cells: Vec<Vec<char>> = (top..top.saturating_add(height))
+            .map(|row| {
+                (left..left.saturating_add(width))
+                    .map(|col| if self.is_paper_roll(row, col) { '@' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        Grid {
+            rows: cells.len(),
+            cols: if cells.is_empty() { 0 } else { width },
+            cells,
+        }
+    }
+
     /// Checks if a position contains a paper roll ('@')
     /// Returns false if position is out of bounds
     pub fn is_paper_roll(&self, row: usize, col: usize) -> bool {
@@ -52,22 +120,9 @@ impl Grid {
     /// Counts the number of paper rolls in the 8 adjacent positions
     /// Handles edge and corner cases with bounds checking
     pub fn count_adjacent_paper_rolls(&self, row: usize, col: usize) -> usize {
-        // Define the 8 direction offsets for adjacent positions
-        // (row_offset, col_offset)
-        let offsets: [(i32, i32); 8] = [
-            (-1, -1), // top-left
-            (-1, 0),  // top
-            (-1, 1),  // top-right
-            (0, -1),  // left
-            (0, 1),   // right
-            (1, -1),  // bottom-left
-            (1, 0),   // bottom
-            (1, 1),   // bottom-right
-        ];
-
         let mut count = 0;
 
-        for (row_offset, col_offset) in offsets.iter() {
+        for (row_offset, col_offset) in NEIGHBOR_OFFSETS.iter() {
             // Calculate the adjacent position with bounds checking
             // Convert usize to i32 for arithmetic, then back to usize if valid
             let adj_row = row as i32 + row_offset;
@@ -102,6 +157,41 @@ impl Grid {
         adjacent_count < 4
     }
 
+    /// Counts the number of adjacent positions that do not hold a paper roll
+    /// Off-grid positions and missing cells past the end of a short row count as empty,
+    /// so this is always 8 minus the number of adjacent paper rolls
+    pub fn count_adjacent_empty(&self, row: usize, col: usize) -> usize {
+        NEIGHBOR_OFFSETS.len() - self.count_adjacent_paper_rolls(row, col)
+    }
+
+    /// Determines if a paper roll is accessible under the minimum-empty-neighbors rule
+    /// A paper roll is accessible if at least min_empty of its 8 adjacent positions are empty
+    /// Returns false for positions that do not contain a paper roll
+    pub fn is_accessible_with_min_empty(&self, row: usize, col: usize, min_empty: usize) -> bool {
+        if !self.is_paper_roll(row, col) {
+            return false;
+        }
+
+        self.count_adjacent_empty(row, col) >= min_empty
+    }
+
+    /// Counts rolls by how many adjacent rolls they have
+    /// Index k holds the number of rolls with exactly k adjacent rolls (0-8)
+    /// Rolls with fewer than 4 neighbors are the accessible ones, so indices 0-3 sum to the accessible count
+    pub fn neighbor_count_histogram(&self) -> [usize; 9] {
+        let mut histogram = [0; 9];
+
+        for row in 0..self.rows {
+            for col in 0..self.cells[row].len() {
+                if self.is_paper_roll(row, col) {
+                    histogram[self.count_adjacent_paper_rolls(row, col)] += 1;
+                }
+            }
+        }
+
+        histogram
+    }
+
     /// Returns the number of rows in the grid
     pub fn rows(&self) -> usize {
         self.rows
@@ -112,21 +202,85 @@ impl Grid {
         self.cols
     }
 
-    /// Removes a paper roll at the specified position by replacing it with empty space
-    /// This method modifies the grid in place
-    pub fn remove_roll(&mut self, row: usize, col: usize) {
-        // Bounds checking
-        if row >= self.rows {
-            return;
+    /// Counts all paper rolls currently in the grid
+    pub fn roll_count(&self) -> usize {
+        let mut count = 0;
+
+        for row in 0..self.rows {
+            for col in 0..self.cells[row].len() {
+                if self.is_paper_roll(row, col) {
+                    count += 1;
+                }
+            }
         }
 
-        // Check if the row has enough columns (handles varying line lengths)
-        if col >= self.cells[row].len() {
-            return;
+        count
+    }
+
+    /// Finds all paper rolls on the outer border of the grid
+    /// The border is the first row, the last row, the first column and the last cell of each row,
+    /// so a short row's last real cell counts as a right border
+    /// Returns a vector of position tuples (row, col) in row-major order
+    pub fn border_rolls(&self) -> Vec<(usize, usize)> {
+        let mut border_positions = Vec::new();
+
+        for row in 0..self.rows {
+            let row_len = self.cells[row].len();
+
+            for col in 0..row_len {
+                let on_border = row == 0 || row == self.rows - 1 || col == 0 || col == row_len - 1;
+
+                if on_border && self.is_paper_roll(row, col) {
+                    border_positions.push((row, col));
+                }
+            }
+        }
+
+        border_positions
+    }
+
+    /// Finds all empty cells that are adjacent to at least one accessible paper roll
+    /// Empty cells are any position within rows() x cols() that does not hold a roll,
+    /// including the missing cells past the end of a short row
+    /// Returns a vector of position tuples (row, col) in row-major order
+    pub fn access_gaps(&self) -> Vec<(usize, usize)> {
+        let mut gap_positions = Vec::new();
+
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                if self.is_paper_roll(row, col) {
+                    continue;
+                }
+
+                let next_to_accessible = NEIGHBOR_OFFSETS.iter().any(|(row_offset, col_offset)| {
+                    let adj_row = row as i32 + row_offset;
+                    let adj_col = col as i32 + col_offset;
+
+                    // is_accessible handles the upper bounds, so only negatives need checking
+                    adj_row >= 0 && adj_col >= 0 && self.is_accessible(adj_row as usize, adj_col as usize)
+                });
+
+                if next_to_accessible {
+                    gap_positions.push((row, col));
+                }
+            }
+        }
+
+        gap_positions
+    }
+
+    /// Removes a paper roll at the specified position by replacing it with empty space
+    /// This method modifies the grid in place
+    /// Returns true if a paper roll was removed, false if the position was empty or out of bounds
+    pub fn remove_roll(&mut self, row: usize, col: usize) -> bool {
+        // Bounds checking and varying line lengths are handled by is_paper_roll
+        if !self.is_paper_roll(row, col) {
+            return false;
         }
 
         // Replace the paper roll with empty space
         self.cells[row][col] = '.';
+        true
     }
 
     /// Finds all currently accessible paper rolls in the grid
@@ -146,4 +300,79 @@ impl Grid {
 
         accessible_positions
     }
+
+    /// Returns how many rolls would be accessible if the roll at (row, col) were removed
+    /// Only the removed roll and its neighbors can change accessibility, so the grid is never mutated
+    /// If the position holds no paper roll the current accessible count is returned
+    pub fn accessible_count_after_removing(&self, row: usize, col: usize) -> usize {
+        let current = self.find_accessible_rolls().len();
+
+        if !self.is_paper_roll(row, col) {
+            return current;
+        }
+
+        // The removed roll no longer counts, even if it was accessible
+        let mut count = current - usize::from(self.is_accessible(row, col));
+
+        for (row_offset, col_offset) in NEIGHBOR_OFFSETS.iter() {
+            let adj_row = row as i32 + row_offset;
+            let adj_col = col as i32 + col_offset;
+
+            if adj_row < 0 || adj_col < 0 {
+                continue;
+            }
+
+            // A neighbor with exactly 4 adjacent rolls drops to 3 and becomes accessible
+            let (adj_row, adj_col) = (adj_row as usize, adj_col as usize);
+            if self.is_paper_roll(adj_row, adj_col) && self.count_adjacent_paper_rolls(adj_row, adj_col) == 4 {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Groups all paper rolls into 8-connected components
+    /// Two rolls belong to the same component if a chain of adjacent rolls (including diagonals) joins them
+    /// Components are ordered by their first roll in row-major order, and each lists its positions in visit order
+    pub fn connected_components(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut visited: Vec<Vec<bool>> = self.cells.iter().map(|row| vec![false; row.len()]).collect();
+        let mut components = Vec::new();
+
+        for row in 0..self.rows {
+            for col in 0..self.cells[row].len() {
+                if visited[row][col] || !self.is_paper_roll(row, col) {
+                    continue;
+                }
+
+                // Flood fill from this roll using an explicit stack
+                let mut component = Vec::new();
+                let mut stack = vec![(row, col)];
+                visited[row][col] = true;
+
+                while let Some((cur_row, cur_col)) = stack.pop() {
+                    component.push((cur_row, cur_col));
+
+                    for (row_offset, col_offset) in NEIGHBOR_OFFSETS.iter() {
+                        let adj_row = cur_row as i32 + row_offset;
+                        let adj_col = cur_col as i32 + col_offset;
+
+                        if adj_row < 0 || adj_col < 0 {
+                            continue;
+                        }
+
+                        let (adj_row, adj_col) = (adj_row as usize, adj_col as usize);
+                        if self.is_paper_roll(adj_row, adj_col) && !visited[adj_row][adj_col] {
+                            visited[adj_row][adj_col] = true;
+                            stack.push((adj_row, adj_col));
+                        }
+                    }
+                }
+
+                components.push(component);
+            }
+        }
+
+        components
+    }
 }
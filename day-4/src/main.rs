@@ -23,8 +23,8 @@ fn main() {
     // Create Grid from file contents for first analysis (immutable reference)
     let grid = grid::Grid::new(input_contents.clone());
 
-    // Call analyzer to count accessible rolls (single-pass analysis)
-    let accessible_count = analyzer::count_accessible_rolls(&grid);
+    // Call analyzer to count accessible rolls (single-pass analysis, split across threads)
+    let accessible_count = analyzer::count_accessible_rolls_parallel(&grid);
 
     // Create a mutable Grid for iterative removal analysis
     let mut grid_for_removal = grid::Grid::new(input_contents);
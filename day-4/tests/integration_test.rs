@@ -1,5 +1,9 @@
-use paper_roll_accessibility::grid::Grid;
-use paper_roll_accessibility::analyzer::count_removable_rolls;
+use paper_roll_accessibility::grid::{Grid, GridError};
+use paper_roll_accessibility::analyzer::{
+    count_accessible_rolls, count_accessible_rolls_parallel, count_component_removals, count_removable_rolls,
+    find_stuck_rolls, remove_accessible_once, removal_counts_per_iteration, removal_depth_map,
+    validate_removal_order,
+};
 
 #[test]
 fn test_count_removable_rolls_simple() {
@@ -55,3 +59,437 @@ fn test_count_removable_rolls_no_paper_rolls() {
 
     assert_eq!(result, 0);
 }
+
+#[test]
+fn test_remove_roll_reports_change() {
+    let input = "\
+@.
+.@";
+
+    let mut grid = Grid::new(input.to_string());
+
+    // Removing a roll succeeds once, then becomes a no-op
+    assert!(grid.remove_roll(0, 0));
+    assert!(!grid.is_paper_roll(0, 0));
+    assert!(!grid.remove_roll(0, 0));
+
+    // Empty and out-of-bounds cells are no-ops
+    assert!(!grid.remove_roll(0, 1));
+    assert!(!grid.remove_roll(5, 5));
+    assert!(grid.is_paper_roll(1, 1));
+}
+
+#[test]
+fn test_remove_accessible_once_steps_match_full_simulation() {
+    let input = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+    let mut stepped_grid = Grid::new(input.to_string());
+    let mut waves = Vec::new();
+    loop {
+        let removed = remove_accessible_once(&mut stepped_grid);
+        if removed == 0 {
+            break;
+        }
+        waves.push(removed);
+    }
+
+    let mut full_grid = Grid::new(input.to_string());
+    let total = count_removable_rolls(&mut full_grid);
+
+    assert_eq!(waves[0], 13);
+    assert_eq!(waves.iter().sum::<usize>(), total);
+    assert_eq!(total, 43);
+
+    // A stable grid stays stable
+    assert_eq!(remove_accessible_once(&mut stepped_grid), 0);
+}
+
+#[test]
+fn test_roll_count_tracks_removals() {
+    // The 3x3 block clears completely
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    assert_eq!(grid.roll_count(), 9);
+    count_removable_rolls(&mut grid);
+    assert_eq!(grid.roll_count(), 0);
+
+    // A solid 5x5 block stalls, leaving rolls behind
+    let input = "@@@@@\n@@@@@\n@@@@@\n@@@@@\n@@@@@";
+    let mut grid = Grid::new(input.to_string());
+    let initial_rolls = grid.roll_count();
+    let removed = count_removable_rolls(&mut grid);
+    assert_eq!(initial_rolls, 25);
+    assert!(removed < initial_rolls);
+    assert_eq!(grid.roll_count(), initial_rolls - removed);
+}
+
+#[test]
+fn test_roll_count_ragged_rows() {
+    let grid = Grid::new("@\n@@@\n.@".to_string());
+    assert_eq!(grid.roll_count(), 5);
+}
+
+#[test]
+fn test_find_stuck_rolls_solid_block() {
+    // In a solid 6x6 block every inner roll keeps at least 4 neighbors
+    // once the corners are gone, so only the corners are ever removed
+    let input = "\
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@";
+
+    let mut grid = Grid::new(input.to_string());
+    let stuck = find_stuck_rolls(&mut grid);
+
+    assert_eq!(stuck.len(), 32);
+    assert!(!stuck.contains(&(0, 0)));
+    assert!(!stuck.contains(&(5, 5)));
+    assert!(stuck.contains(&(2, 2)));
+    assert_eq!(stuck.len(), grid.roll_count());
+}
+
+#[test]
+fn test_find_stuck_rolls_fully_clearing_grid() {
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    assert!(find_stuck_rolls(&mut grid).is_empty());
+}
+
+#[test]
+fn test_count_accessible_rolls_parallel_matches_serial() {
+    let fixtures = [
+        "",
+        "...\n...\n...",
+        "@@@\n@@@\n@@@",
+        "@\n@@@@\n.@\n@@@@@@@",
+        "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.",
+    ];
+
+    for input in fixtures {
+        let grid = Grid::new(input.to_string());
+        assert_eq!(count_accessible_rolls_parallel(&grid), count_accessible_rolls(&grid), "input: {:?}", input);
+    }
+}
+
+#[test]
+fn test_border_rolls() {
+    let input = "\
+@@@@
+@@@@
+@@@@
+@..@";
+
+    let grid = Grid::new(input.to_string());
+    let border = grid.border_rolls();
+
+    assert_eq!(border.len(), 10);
+    assert!(!border.contains(&(1, 1)));
+    assert!(!border.contains(&(2, 2)));
+    assert!(border.contains(&(3, 3)));
+}
+
+#[test]
+fn test_border_rolls_ragged_rows() {
+    // The middle row is short, so its last cell is a right border
+    let input = "\
+.....
+@@@
+.@@@.";
+
+    let grid = Grid::new(input.to_string());
+    assert_eq!(grid.border_rolls(), vec![(1, 0), (1, 2), (2, 1), (2, 2), (2, 3)]);
+}
+
+#[test]
+fn test_connected_components() {
+    // Diagonal contact joins rolls; the bottom-right pair is separate
+    let grid = Grid::new("@..\n.@.\n...\n.@@".to_string());
+    let components = grid.connected_components();
+
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].len(), 2);
+    assert!(components[0].contains(&(0, 0)));
+    assert!(components[0].contains(&(1, 1)));
+    assert_eq!(components[1].len(), 2);
+    assert!(Grid::new(String::new()).connected_components().is_empty());
+}
+
+#[test]
+fn test_count_component_removals_clears_solid_block() {
+    // Cell-by-cell removal leaves 32 stuck rolls, but the block is one component
+    // with accessible corners, so it is removed in a single round
+    let input = "\
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@
+@@@@@@";
+
+    let mut grid = Grid::new(input.to_string());
+    assert_eq!(count_component_removals(&mut grid), 36);
+    assert_eq!(grid.roll_count(), 0);
+}
+
+#[test]
+fn test_count_component_removals_keeps_fully_blocked_component() {
+    // Every roll in the octagon has at least 4 neighbours, so it is never removed,
+    // while the lone roll on the right is accessible and goes in the first round
+    let input = "\
+.@@@...
+@@@@@..
+@@@@@.@
+@@@@@..
+.@@@...";
+
+    let mut grid = Grid::new(input.to_string());
+
+    assert_eq!(count_component_removals(&mut grid), 1);
+    assert_eq!(grid.roll_count(), 21);
+    assert!(!grid.is_paper_roll(2, 6));
+}
+
+#[test]
+fn test_removal_counts_per_iteration_full_3x3() {
+    // Corners go first (3 neighbors each), then the edges, and finally the center
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    assert_eq!(removal_counts_per_iteration(&mut grid), vec![4, 4, 1]);
+    assert_eq!(grid.roll_count(), 0);
+}
+
+#[test]
+fn test_removal_counts_per_iteration_sums_to_total() {
+    let input = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
+
+    let mut grid = Grid::new(input.to_string());
+    let counts = removal_counts_per_iteration(&mut grid);
+
+    let mut total_grid = Grid::new(input.to_string());
+    assert_eq!(counts.iter().sum::<usize>(), count_removable_rolls(&mut total_grid));
+    assert_eq!(counts[0], 13);
+    assert!(removal_counts_per_iteration(&mut Grid::new(String::new())).is_empty());
+}
+
+#[test]
+fn test_access_gaps() {
+    // Only the lone roll at (0, 0) is accessible; the 3x3 block's center is not,
+    // but its corners are, so gaps appear around both
+    let grid = Grid::new("@....\n.....\n..@@@\n..@@@\n..@@@".to_string());
+    let gaps = grid.access_gaps();
+
+    assert!(gaps.contains(&(0, 1)));
+    assert!(gaps.contains(&(1, 0)));
+    assert!(gaps.contains(&(1, 1)));
+    assert!(gaps.contains(&(1, 2)));
+    assert!(!gaps.contains(&(0, 4)));
+    assert!(gaps.iter().all(|&(row, col)| !grid.is_paper_roll(row, col)));
+    assert!(gaps.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_access_gaps_ragged_rows() {
+    // (1, 1) and (1, 2) lie past the end of the short middle row
+    let grid = Grid::new("@@@\n@\n@@@".to_string());
+    let gaps = grid.access_gaps();
+
+    assert_eq!(gaps, vec![(1, 1), (1, 2)]);
+    assert!(Grid::new(String::new()).access_gaps().is_empty());
+}
+
+#[test]
+fn test_validate_removal_order_accepts_valid_order() {
+    // Corners, then edges, then the center
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    let order = [
+        (0, 0), (0, 2), (2, 0), (2, 2),
+        (0, 1), (1, 0), (1, 2), (2, 1),
+        (1, 1),
+    ];
+
+    assert_eq!(validate_removal_order(&mut grid, &order), Ok(()));
+    assert_eq!(grid.roll_count(), 0);
+}
+
+#[test]
+fn test_validate_removal_order_reports_first_bad_index() {
+    // The center is still surrounded after only one corner is removed
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    assert_eq!(validate_removal_order(&mut grid, &[(0, 0), (1, 1)]), Err(1));
+    assert_eq!(grid.roll_count(), 8);
+
+    // Removing the same roll twice fails because it is no longer a roll
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    assert_eq!(validate_removal_order(&mut grid, &[(0, 0), (0, 0)]), Err(1));
+
+    // Out-of-bounds positions are rejected
+    let mut grid = Grid::new("@".to_string());
+    assert_eq!(validate_removal_order(&mut grid, &[(5, 5)]), Err(0));
+}
+
+#[test]
+fn test_count_adjacent_empty() {
+    let grid = Grid::new("@@@\n@@@\n@@@".to_string());
+
+    // Interior of a solid block has no empty neighbors
+    assert_eq!(grid.count_adjacent_empty(1, 1), 0);
+    // Off-grid neighbors count as empty
+    assert_eq!(grid.count_adjacent_empty(0, 0), 5);
+    assert_eq!(grid.count_adjacent_empty(0, 1), 3);
+
+    // Missing cells in a short row count as empty too
+    let ragged = Grid::new("@@@\n@\n@@@".to_string());
+    assert_eq!(ragged.count_adjacent_empty(1, 0), 4);
+}
+
+#[test]
+fn test_is_accessible_with_min_empty() {
+    let grid = Grid::new("@@@\n@@@\n@@@".to_string());
+
+    assert!(!grid.is_accessible_with_min_empty(1, 1, 1));
+    assert!(grid.is_accessible_with_min_empty(1, 1, 0));
+    assert!(grid.is_accessible_with_min_empty(0, 0, 5));
+    assert!(!grid.is_accessible_with_min_empty(0, 0, 6));
+
+    // Requiring 5 empty neighbors matches the default "fewer than 4 rolls" rule
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            assert_eq!(grid.is_accessible_with_min_empty(row, col, 5), grid.is_accessible(row, col));
+        }
+    }
+
+    // Empty positions are never accessible
+    let sparse = Grid::new("@.".to_string());
+    assert!(!sparse.is_accessible_with_min_empty(0, 1, 0));
+}
+
+#[test]
+fn test_new_strict_accepts_valid_grid() {
+    let grid = Grid::new_strict("@.@\n.@.\n@@".to_string()).unwrap();
+    assert_eq!(grid.rows(), 3);
+    assert_eq!(grid.roll_count(), 5);
+    assert!(Grid::new_strict(String::new()).is_ok());
+}
+
+#[test]
+fn test_new_strict_reports_first_unexpected_character() {
+    let err = Grid::new_strict("@.@\n.@x\n#@".to_string()).err().unwrap();
+    assert_eq!(err, GridError::UnexpectedCharacter { row: 1, col: 2, character: 'x' });
+    assert_eq!(err.to_string(), "Unexpected character 'x' at row 1, column 2");
+
+    // The lenient constructor still accepts the same input
+    assert_eq!(Grid::new("@.@\n.@x\n#@".to_string()).roll_count(), 4);
+}
+
+#[test]
+fn test_accessible_count_after_removing_matches_actual_removal() {
+    let input = "@@@@\n@@@@\n@@@.\n.@@@".to_string();
+    let grid = Grid::new(input.clone());
+
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let mut removed = Grid::new(input.clone());
+            removed.remove_roll(row, col);
+
+            assert_eq!(
+                grid.accessible_count_after_removing(row, col),
+                removed.find_accessible_rolls().len(),
+                "mismatch removing ({}, {})", row, col
+            );
+        }
+    }
+
+    // The original grid is left untouched
+    assert_eq!(grid.roll_count(), 14);
+}
+
+#[test]
+fn test_accessible_count_after_removing_empty_position() {
+    let grid = Grid::new("@@@\n@.@\n@@@".to_string());
+    assert_eq!(grid.accessible_count_after_removing(1, 1), grid.find_accessible_rolls().len());
+    assert_eq!(grid.accessible_count_after_removing(5, 5), grid.find_accessible_rolls().len());
+}
+
+#[test]
+fn test_removal_depth_map_full_square() {
+    let mut grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    let depths = removal_depth_map(&mut grid);
+
+    // Corners have 3 neighbors and go first; edges drop to 3 neighbors next, then the center
+    for corner in [(0, 0), (0, 2), (2, 0), (2, 2)] {
+        assert_eq!(depths[&corner], 0);
+    }
+    for edge in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+        assert_eq!(depths[&edge], 1);
+    }
+    assert_eq!(depths[&(1, 1)], 2);
+    assert_eq!(depths.len(), 9);
+}
+
+#[test]
+fn test_removal_depth_map_matches_wave_counts_and_skips_stuck() {
+    let input = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
+    let counts = removal_counts_per_iteration(&mut Grid::new(input.to_string()));
+    let stuck = find_stuck_rolls(&mut Grid::new(input.to_string()));
+    let depths = removal_depth_map(&mut Grid::new(input.to_string()));
+
+    for (depth, &count) in counts.iter().enumerate() {
+        assert_eq!(depths.values().filter(|&&d| d == depth).count(), count);
+    }
+    assert!(stuck.iter().all(|position| !depths.contains_key(position)));
+}
+
+#[test]
+fn test_crop_extracts_region_and_pads_out_of_bounds() {
+    let grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    let cropped = grid.crop(1, 1, 3, 3);
+
+    assert_eq!(cropped.rows(), 3);
+    assert_eq!(cropped.cols(), 3);
+    assert_eq!(cropped.roll_count(), 4);
+    assert!(cropped.is_paper_roll(0, 0));
+    assert!(cropped.is_paper_roll(1, 1));
+    assert!(!cropped.is_paper_roll(2, 2));
+
+    let empty = grid.crop(10, 10, 0, 5);
+    assert_eq!(empty.rows(), 0);
+    assert_eq!(empty.roll_count(), 0);
+}
+
+#[test]
+fn test_crop_boundary_acts_as_grid_edge() {
+    let grid = Grid::new("@@@\n@@@\n@@@".to_string());
+
+    // The center has 8 neighbors in the full grid but only 3 inside the top-left quadrant
+    assert!(!grid.is_accessible(1, 1));
+    let quadrant = grid.crop(0, 0, 2, 2);
+    assert!(quadrant.is_accessible(1, 1));
+    assert_eq!(count_accessible_rolls(&quadrant), 4);
+}
+
+#[test]
+fn test_neighbor_count_histogram() {
+    let grid = Grid::new("@@@\n@@@\n@@@".to_string());
+    // 4 corners with 3 neighbors, 4 edges with 5 and the center with 8
+    assert_eq!(grid.neighbor_count_histogram(), [0, 0, 0, 4, 0, 4, 0, 0, 1]);
+    assert_eq!(Grid::new(String::new()).neighbor_count_histogram(), [0; 9]);
+}
+
+#[test]
+fn test_neighbor_count_histogram_cross_checks_accessible_count() {
+    let grid = Grid::new("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.".to_string());
+    let histogram = grid.neighbor_count_histogram();
+
+    assert_eq!(histogram[..4].iter().sum::<usize>(), count_accessible_rolls(&grid));
+    assert_eq!(histogram.iter().sum::<usize>(), grid.roll_count());
+}
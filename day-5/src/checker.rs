@@ -2,6 +2,7 @@
 // Contains core business logic for determining ingredient freshness
 
 use crate::parser::{FreshRange, InventoryData};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Counts the number of fresh ingredients from the available ingredients
 ///
@@ -22,6 +23,162 @@ pub fn count_fresh_ingredients(data: &InventoryData) -> usize {
         .count()
 }
 
+/// Counts the number of spoiled ingredients from the available ingredients
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The count of ingredients outside every fresh range, where duplicated IDs count
+///   each time they appear
+///
+/// # Requirements
+/// * Always equals `available_ingredients.len() - count_fresh_ingredients(data)`
+pub fn count_spoiled_ingredients(data: &InventoryData) -> usize {
+    data.available_ingredients
+        .iter()
+        .filter(|&&ingredient_id| !is_fresh(ingredient_id, &data.fresh_ranges))
+        .count()
+}
+
+/// Sums the IDs of the fresh ingredients from the available ingredients
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The sum of every fresh ingredient ID, where duplicated IDs are added each time
+///   they appear (as in `count_fresh_ingredients`)
+///
+/// # Requirements
+/// * Accumulates in `u128`, so the sum cannot overflow even when every ID is near `u64::MAX`
+pub fn sum_fresh_ingredients(data: &InventoryData) -> u128 {
+    data.available_ingredients
+        .iter()
+        .filter(|&&ingredient_id| is_fresh(ingredient_id, &data.fresh_ranges))
+        .map(|&ingredient_id| ingredient_id as u128)
+        .sum()
+}
+
+/// Computes the share of available ingredients that are fresh, as a percentage
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * `100 * fresh / total` between 0.0 and 100.0, where duplicated IDs count each time
+///   they appear (as in `count_fresh_ingredients`)
+/// * 0.0 when there are no available ingredients
+pub fn fresh_percentage(data: &InventoryData) -> f64 {
+    if data.available_ingredients.is_empty() {
+        return 0.0;
+    }
+
+    100.0 * count_fresh_ingredients(data) as f64 / data.available_ingredients.len() as f64
+}
+
+/// Finds the first available ingredient that is fresh
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * `Some(id)` for the first fresh ingredient in input order
+/// * `None` if no available ingredient is fresh
+///
+/// # Requirements
+/// * Stops at the first match instead of checking every ingredient
+pub fn first_fresh_ingredient(data: &InventoryData) -> Option<u64> {
+    data.available_ingredients
+        .iter()
+        .copied()
+        .find(|&ingredient_id| is_fresh(ingredient_id, &data.fresh_ranges))
+}
+
+/// Counts the available ingredients that fall within each fresh range
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * One count per fresh range, in input order
+///
+/// # Requirements
+/// * An ingredient inside several overlapping ranges counts toward each of them,
+///   so the counts may sum to more than `count_fresh_ingredients`
+pub fn count_fresh_per_range(data: &InventoryData) -> Vec<usize> {
+    data.fresh_ranges
+        .iter()
+        .map(|range| {
+            data.available_ingredients
+                .iter()
+                .filter(|&&ingredient_id| ingredient_id >= range.start && ingredient_id <= range.end)
+                .count()
+        })
+        .collect()
+}
+
+/// Counts the available ingredients that fall within exactly one fresh range
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The number of available ingredients covered by precisely one range, where
+///   duplicated IDs count each time they appear
+///
+/// # Requirements
+/// * Every range is checked for each ingredient rather than stopping at the first
+///   match, so an ID inside two overlapping (or repeated) ranges is not counted
+pub fn count_fresh_in_exactly_one(data: &InventoryData) -> usize {
+    data.available_ingredients
+        .iter()
+        .filter(|&ingredient_id| {
+            data.fresh_ranges
+                .iter()
+                .filter(|range| range.as_range().contains(ingredient_id))
+                .count()
+                == 1
+        })
+        .count()
+}
+
+/// Finds available ingredient IDs that appear more than once
+///
+/// # Arguments
+/// * `data` - The InventoryData containing the available ingredients
+///
+/// # Returns
+/// * Each duplicated ID once, in ascending order (empty if all IDs are unique)
+pub fn find_duplicate_ingredients(data: &InventoryData) -> Vec<u64> {
+    let mut occurrences: BTreeMap<u64, usize> = BTreeMap::new();
+    for &ingredient_id in &data.available_ingredients {
+        *occurrences.entry(ingredient_id).or_insert(0) += 1;
+    }
+
+    occurrences
+        .into_iter()
+        .filter(|&(_, count)| count > 1)
+        .map(|(ingredient_id, _)| ingredient_id)
+        .collect()
+}
+
+/// Counts the distinct fresh ingredients from the available ingredients
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The count of fresh ingredient IDs, counting each duplicated ID only once
+pub fn count_distinct_fresh_ingredients(data: &InventoryData) -> usize {
+    data.available_ingredients
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|&&ingredient_id| is_fresh(ingredient_id, &data.fresh_ranges))
+        .count()
+}
+
 /// Checks if an ingredient ID is fresh based on the provided fresh ranges
 ///
 /// # Arguments
@@ -42,6 +199,79 @@ pub fn is_fresh(ingredient_id: u64, ranges: &[FreshRange]) -> bool {
     })
 }
 
+/// Checks if an ingredient ID falls within every one of the provided fresh ranges
+///
+/// # Arguments
+/// * `ingredient_id` - The ingredient ID to check
+/// * `ranges` - A slice of FreshRange structs defining fresh ingredient ranges
+///
+/// # Returns
+/// * `true` if the ingredient ID falls within all ranges (inclusive boundaries)
+/// * `false` if the ingredient ID is outside at least one range, or if `ranges` is empty
+///
+/// # Requirements
+/// * Uses inclusive boundary checking: start <= id <= end
+/// * An empty range list certifies nothing, so no ID is fresh in all of zero ranges
+pub fn is_fresh_in_all(ingredient_id: u64, ranges: &[FreshRange]) -> bool {
+    !ranges.is_empty()
+        && ranges.iter().all(|range| {
+            ingredient_id >= range.start && ingredient_id <= range.end
+        })
+}
+
+/// Counts the available ingredients that fall within every fresh range
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The count of available ingredients that are fresh in all ranges
+///   (0 when there are no ranges, see `is_fresh_in_all`)
+pub fn count_fresh_in_all(data: &InventoryData) -> usize {
+    data.available_ingredients
+        .iter()
+        .filter(|&&ingredient_id| is_fresh_in_all(ingredient_id, &data.fresh_ranges))
+        .count()
+}
+
+/// Finds the fresh ingredient ID closest to the given ID
+///
+/// # Arguments
+/// * `id` - The ingredient ID to look up
+/// * `ranges` - A slice of FreshRange structs defining fresh ingredient ranges
+///
+/// # Returns
+/// * `Some(id)` if the ID is already fresh
+/// * `Some(nearest)` with the smallest absolute distance otherwise, ties resolving to the lower ID
+/// * `None` only when there are no ranges
+///
+/// # Requirements
+/// * Binary-searches the merged, sorted ranges for the intervals surrounding `id`
+pub fn nearest_fresh_id(id: u64, ranges: &[FreshRange]) -> Option<u64> {
+    let merged = merge_ranges(ranges);
+
+    // Index of the first merged range that ends at or after `id`
+    let index = merged.partition_point(|range| range.end < id);
+
+    let above = merged.get(index).map(|range| range.start);
+    if above.is_some_and(|start| start <= id) {
+        return Some(id);
+    }
+
+    let below = index.checked_sub(1).map(|previous| merged[previous].end);
+
+    match (below, above) {
+        (Some(below), Some(above)) => {
+            if id - below <= above - id {
+                Some(below)
+            } else {
+                Some(above)
+            }
+        }
+        (below, above) => below.or(above),
+    }
+}
+
 /// Counts the total number of unique ingredient IDs across all fresh ranges
 ///
 /// # Arguments
@@ -50,42 +280,252 @@ pub fn is_fresh(ingredient_id: u64, ranges: &[FreshRange]) -> bool {
 /// # Returns
 /// * The total count of unique ingredient IDs that fall within any range
 ///
+/// # Overflow
+/// * The count is a `usize`, so a range covering more IDs than `usize::MAX`
+///   (easily reached on 32-bit targets) wraps or panics. Use
+///   `count_total_fresh_in_ranges_u128` when ranges may be wide.
+///
 /// # Requirements
 /// * Merges overlapping/adjacent ranges to avoid counting duplicates
 /// * Calculates count by summing the size of each merged range
 /// * Handles overlapping ranges by deduplicating IDs
 /// * Requirements: 4.1, 4.2, 4.3, 4.5
 pub fn count_total_fresh_in_ranges(ranges: &[FreshRange]) -> usize {
+    // Calculate total count by summing the size of each merged range
+    merge_ranges(ranges)
+        .iter()
+        .map(|range| (range.end - range.start + 1) as usize)
+        .sum()
+}
+
+/// Counts the total number of unique ingredient IDs across all fresh ranges as a `u128`
+///
+/// # Arguments
+/// * `ranges` - A slice of FreshRange structs defining fresh ingredient ranges
+///
+/// # Returns
+/// * The total count of unique ingredient IDs that fall within any range
+///
+/// # Requirements
+/// * Same merging behaviour as `count_total_fresh_in_ranges`
+/// * Sizes are summed as `u128`, so the result is exact regardless of target
+///   pointer width, even for a single range spanning every `u64`
+pub fn count_total_fresh_in_ranges_u128(ranges: &[FreshRange]) -> u128 {
+    merge_ranges(ranges)
+        .iter()
+        .map(|range| (range.end - range.start) as u128 + 1)
+        .sum()
+}
+
+/// Counts the distinct fresh IDs across the ranges and the available ingredients
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The size of the union of the range coverage and the fresh available ingredients
+///
+/// # Requirements
+/// * Spoiled available ingredients are excluded, and every fresh available ingredient
+///   already lies within a range, so the union is just the merged range coverage and
+///   this always equals `count_total_fresh_in_ranges_u128` (see
+///   `count_fresh_available_outside_ranges`)
+pub fn count_distinct_fresh_total(data: &InventoryData) -> u128 {
+    count_total_fresh_in_ranges_u128(&data.fresh_ranges)
+}
+
+/// Counts fresh available ingredients that fall outside the merged range coverage
+///
+/// # Arguments
+/// * `data` - The InventoryData containing fresh ranges and available ingredients
+///
+/// # Returns
+/// * The number of available ingredients that `is_fresh` accepts but that no merged
+///   range covers
+///
+/// # Requirements
+/// * Sanity check for `count_distinct_fresh_total`: merging never drops coverage,
+///   so this is always 0
+pub fn count_fresh_available_outside_ranges(data: &InventoryData) -> usize {
+    let merged = merge_ranges(&data.fresh_ranges);
+
+    data.available_ingredients
+        .iter()
+        .filter(|&&ingredient_id| {
+            is_fresh(ingredient_id, &data.fresh_ranges) && !is_fresh(ingredient_id, &merged)
+        })
+        .count()
+}
+
+/// Iterates over every ingredient ID covered by the fresh ranges
+///
+/// # Arguments
+/// * `ranges` - A slice of FreshRange structs defining fresh ingredient ranges
+///
+/// # Returns
+/// * An iterator yielding each covered ID once, in ascending order
+///
+/// # Requirements
+/// * Ranges are merged first, so overlapping ranges do not yield duplicates
+/// * IDs are produced lazily; only the merged ranges are allocated
+/// * The number of items equals `count_total_fresh_in_ranges`, which can be
+///   astronomically large. Use `fresh_ids_iter_bounded` to refuse such input.
+pub fn fresh_ids_iter(ranges: &[FreshRange]) -> impl Iterator<Item = u64> {
+    merge_ranges(ranges)
+        .into_iter()
+        .flat_map(|range| range.start..=range.end)
+}
+
+/// Iterates over every covered ingredient ID, provided there are at most `limit` of them
+///
+/// # Arguments
+/// * `ranges` - A slice of FreshRange structs defining fresh ingredient ranges
+/// * `limit` - The largest number of IDs the caller is willing to enumerate
+///
+/// # Returns
+/// * `Some(iterator)` - Same as `fresh_ids_iter` when the total fits within `limit`
+/// * `None` - If the ranges cover more than `limit` IDs
+pub fn fresh_ids_iter_bounded(ranges: &[FreshRange], limit: u64) -> Option<impl Iterator<Item = u64>> {
+    if count_total_fresh_in_ranges_u128(ranges) > limit as u128 {
+        return None;
+    }
+
+    Some(fresh_ids_iter(ranges))
+}
+
+/// Merges overlapping and adjacent ranges into a sorted list of disjoint ranges
+///
+/// # Arguments
+/// * `ranges` - A slice of FreshRange structs in any order
+///
+/// # Returns
+/// * Disjoint ranges sorted by start, covering exactly the same IDs as the input
+///
+/// # Requirements
+/// * Adjacent ranges (e.g. 1-3 and 4-6) are merged into one
+pub fn merge_ranges(ranges: &[FreshRange]) -> Vec<FreshRange> {
     if ranges.is_empty() {
-        return 0;
+        return Vec::new();
     }
 
     // Sort ranges by start position
     let mut sorted_ranges: Vec<FreshRange> = ranges.to_vec();
-    sorted_ranges.sort_by_key(|r| r.start);
+    sorted_ranges.sort();
 
     // Merge overlapping and adjacent ranges
     let mut merged: Vec<FreshRange> = Vec::new();
-    let mut current = sorted_ranges[0].clone();
+    let mut current = sorted_ranges[0];
 
     for range in sorted_ranges.iter().skip(1) {
         // Check if ranges overlap or are adjacent
         // Adjacent means end + 1 == start (e.g., 1-3 and 4-6)
-        if range.start <= current.end + 1 {
+        if range.start <= current.end.saturating_add(1) {
             // Merge by extending the current range
             current.end = current.end.max(range.end);
         } else {
             // No overlap, save current and start new range
             merged.push(current);
-            current = range.clone();
+            current = *range;
         }
     }
     merged.push(current);
 
-    // Calculate total count by summing the size of each merged range
-    merged.iter()
-        .map(|range| (range.end - range.start + 1) as usize)
-        .sum()
+    merged
+}
+
+/// Combines two inventory shards and merges their overlapping or adjacent ranges
+///
+/// # Arguments
+/// * `first` - The shard whose ingredients come first
+/// * `second` - The shard whose ingredients come second
+///
+/// # Returns
+/// * InventoryData whose fresh ranges are sorted and non-overlapping (see
+///   `merge_ranges`), with available ingredients concatenated in order
+pub fn merge_and_normalize(first: InventoryData, second: InventoryData) -> InventoryData {
+    let mut merged = first.merge(second);
+    merged.fresh_ranges = merge_ranges(&merged.fresh_ranges);
+    merged
+}
+
+/// Checks if one range fully contains another
+///
+/// # Arguments
+/// * `outer` - The candidate containing range
+/// * `inner` - The range that should lie within `outer`
+///
+/// # Returns
+/// * `true` if every ID in `inner` is also in `outer` (inclusive boundaries)
+pub fn range_contains(outer: &FreshRange, inner: &FreshRange) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Checks if every ID covered by one set of ranges is also covered by another
+///
+/// # Arguments
+/// * `a` - The ranges whose IDs must all be covered
+/// * `b` - The ranges that must cover them
+///
+/// # Returns
+/// * `true` if the IDs covered by `a` are a subset of those covered by `b`
+///
+/// # Requirements
+/// * Both sets are merged first, so overlapping or adjacent ranges in `b` can
+///   jointly cover a single range in `a`
+/// * An empty `a` is a subset of anything; equal merged sets are mutual subsets
+pub fn is_subset(a: &[FreshRange], b: &[FreshRange]) -> bool {
+    let merged_b = merge_ranges(b);
+
+    // Each merged range of `a` is contiguous, so it must sit inside a single
+    // merged range of `b` (merged ranges of `b` have gaps between them)
+    merge_ranges(a)
+        .iter()
+        .all(|inner| merged_b.iter().any(|outer| range_contains(outer, inner)))
+}
+
+/// Computes the IDs covered by one set of ranges but not by another
+///
+/// # Arguments
+/// * `a` - The ranges to take IDs from
+/// * `b` - The ranges whose IDs are removed
+///
+/// # Returns
+/// * Merged, sorted ranges covering exactly the IDs in `a` that are not in `b`
+///
+/// # Examples
+/// * a = [1-10], b = [4-6] -> [1-3, 7-10]
+pub fn range_difference(a: &[FreshRange], b: &[FreshRange]) -> Vec<FreshRange> {
+    let merged_b = merge_ranges(b);
+    let mut difference = Vec::new();
+
+    for range in merge_ranges(a) {
+        // Walk the removed ranges, keeping the uncovered pieces of `range`
+        let mut cursor = Some(range.start);
+
+        for removed in merged_b.iter() {
+            let Some(start) = cursor else { break };
+            if removed.start > range.end {
+                break;
+            }
+            if removed.end < start {
+                continue;
+            }
+
+            if removed.start > start {
+                difference.push(FreshRange { start, end: removed.start - 1 });
+            }
+            // Nothing remains once a removed range reaches the top of the ID space
+            cursor = removed.end.checked_add(1);
+        }
+
+        if let Some(start) = cursor {
+            if start <= range.end {
+                difference.push(FreshRange { start, end: range.end });
+            }
+        }
+    }
+
+    difference
 }
 
 #[cfg(test)]
@@ -249,6 +689,155 @@ mod tests {
         assert_eq!(count_fresh_ingredients(&data), 0);
     }
 
+    #[test]
+    fn test_count_distinct_fresh_total_matches_range_coverage() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 3, end: 5 },
+                FreshRange { start: 10, end: 14 },
+                FreshRange { start: 16, end: 20 },
+                FreshRange { start: 12, end: 18 },
+            ],
+            available_ingredients: vec![1, 5, 8, 11, 17, 32],
+        };
+
+        assert_eq!(count_distinct_fresh_total(&data), 14);
+        assert_eq!(count_distinct_fresh_total(&data), count_total_fresh_in_ranges_u128(&data.fresh_ranges));
+        assert_eq!(count_fresh_available_outside_ranges(&data), 0);
+    }
+
+    #[test]
+    fn test_fresh_percentage() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 3, end: 5 },
+                FreshRange { start: 10, end: 14 },
+                FreshRange { start: 16, end: 20 },
+                FreshRange { start: 12, end: 18 },
+            ],
+            available_ingredients: vec![1, 5, 8, 11, 17, 32],
+        };
+        assert_eq!(fresh_percentage(&data), 50.0);
+
+        let empty = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 3, end: 5 }],
+            available_ingredients: vec![],
+        };
+        assert_eq!(fresh_percentage(&empty), 0.0);
+    }
+
+    #[test]
+    fn test_first_fresh_ingredient() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 3, end: 5 },
+                FreshRange { start: 10, end: 14 },
+            ],
+            available_ingredients: vec![1, 11, 8, 5],
+        };
+        assert_eq!(first_fresh_ingredient(&data), Some(11));
+
+        let none_fresh = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 3, end: 5 }],
+            available_ingredients: vec![1, 8, 32],
+        };
+        assert_eq!(first_fresh_ingredient(&none_fresh), None);
+    }
+
+    #[test]
+    fn test_count_fresh_per_range_overlapping_ranges() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 3, end: 5 },
+                FreshRange { start: 10, end: 14 },
+                FreshRange { start: 16, end: 20 },
+                FreshRange { start: 12, end: 18 },
+            ],
+            available_ingredients: vec![1, 5, 8, 11, 17, 32],
+        };
+
+        // 17 is in both 16-20 and 12-18
+        assert_eq!(count_fresh_per_range(&data), vec![1, 1, 1, 1]);
+        assert_eq!(count_fresh_ingredients(&data), 3);
+    }
+
+    #[test]
+    fn test_count_fresh_per_range_no_ranges() {
+        let data = InventoryData {
+            fresh_ranges: vec![],
+            available_ingredients: vec![1, 2, 3],
+        };
+
+        assert!(count_fresh_per_range(&data).is_empty());
+    }
+
+    #[test]
+    fn test_is_fresh_in_all_overlapping_ranges() {
+        let ranges = vec![
+            FreshRange { start: 100, end: 200 },
+            FreshRange { start: 150, end: 250 },
+        ];
+
+        // Only IDs in the intersection 150-200 qualify
+        assert!(is_fresh_in_all(150, &ranges));
+        assert!(is_fresh_in_all(200, &ranges));
+        assert!(!is_fresh_in_all(149, &ranges));
+        assert!(!is_fresh_in_all(201, &ranges));
+    }
+
+    #[test]
+    fn test_is_fresh_in_all_empty_ranges() {
+        // No ranges means nothing is certified fresh
+        assert!(!is_fresh_in_all(100, &[]));
+    }
+
+    #[test]
+    fn test_count_fresh_in_all() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 100, end: 200 },
+                FreshRange { start: 150, end: 250 },
+            ],
+            available_ingredients: vec![125, 150, 175, 200, 225],
+        };
+
+        // 150, 175 and 200 are in both ranges
+        assert_eq!(count_fresh_in_all(&data), 3);
+        assert_eq!(count_fresh_ingredients(&data), 5);
+    }
+
+    #[test]
+    fn test_find_duplicate_ingredients() {
+        let data = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 100, end: 200 }],
+            available_ingredients: vec![350, 150, 500, 150, 350, 150, 42],
+        };
+
+        assert_eq!(find_duplicate_ingredients(&data), vec![150, 350]);
+    }
+
+    #[test]
+    fn test_find_duplicate_ingredients_unique() {
+        let data = InventoryData {
+            fresh_ranges: vec![],
+            available_ingredients: vec![1, 2, 3],
+        };
+
+        assert!(find_duplicate_ingredients(&data).is_empty());
+    }
+
+    #[test]
+    fn test_count_distinct_fresh_ingredients() {
+        let data = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 100, end: 200 }],
+            available_ingredients: vec![150, 150, 175, 500, 500],
+        };
+
+        // Duplicates are counted multiple times by the plain count
+        assert_eq!(count_fresh_ingredients(&data), 3);
+        assert_eq!(count_distinct_fresh_ingredients(&data), 2);
+    }
+
     #[test]
     fn test_count_total_fresh_in_ranges_non_overlapping() {
         let ranges = vec![
@@ -326,4 +915,269 @@ mod tests {
         // Total: 11 unique IDs
         assert_eq!(count_total_fresh_in_ranges(&ranges), 11);
     }
+
+    #[test]
+    fn test_count_total_fresh_in_ranges_u128_matches_usize() {
+        let ranges = vec![
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 10, end: 14 },
+            FreshRange { start: 16, end: 20 },
+            FreshRange { start: 12, end: 18 },
+        ];
+
+        assert_eq!(count_total_fresh_in_ranges_u128(&ranges), 14);
+        assert_eq!(count_total_fresh_in_ranges_u128(&[]), 0);
+    }
+
+    #[test]
+    fn test_count_total_fresh_in_ranges_u128_full_domain() {
+        let ranges = vec![
+            FreshRange { start: 0, end: u64::MAX },
+            FreshRange { start: 208521390563908, end: 208521390563908 },
+        ];
+
+        // Every u64 is covered: 2^64 IDs, one more than u64::MAX
+        assert_eq!(count_total_fresh_in_ranges_u128(&ranges), 1u128 << 64);
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = vec![
+            FreshRange { start: 16, end: 20 },
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 12, end: 15 },
+            FreshRange { start: 10, end: 14 },
+        ];
+
+        assert_eq!(merge_ranges(&ranges), vec![
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 10, end: 20 },
+        ]);
+        assert!(merge_ranges(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_and_normalize() {
+        let north = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 10, end: 14 }],
+            available_ingredients: vec![1, 12],
+        };
+        let south = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 3, end: 5 }, FreshRange { start: 12, end: 18 }],
+            available_ingredients: vec![12, 4],
+        };
+
+        let normalized = merge_and_normalize(north, south);
+        assert_eq!(normalized.fresh_ranges, vec![
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 10, end: 18 },
+        ]);
+        assert_eq!(normalized.available_ingredients, vec![1, 12, 12, 4]);
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let outer = FreshRange { start: 10, end: 20 };
+
+        assert!(range_contains(&outer, &FreshRange { start: 10, end: 20 }));
+        assert!(range_contains(&outer, &FreshRange { start: 12, end: 18 }));
+        assert!(!range_contains(&outer, &FreshRange { start: 9, end: 15 }));
+        assert!(!range_contains(&outer, &FreshRange { start: 15, end: 21 }));
+    }
+
+    #[test]
+    fn test_is_subset_across_merged_ranges() {
+        // 5-15 is covered by 1-10 and 11-20 together
+        let a = vec![FreshRange { start: 5, end: 15 }];
+        let b = vec![
+            FreshRange { start: 11, end: 20 },
+            FreshRange { start: 1, end: 10 },
+        ];
+        assert!(is_subset(&a, &b));
+        assert!(!is_subset(&b, &a));
+
+        // A gap in b breaks coverage
+        let b_with_gap = vec![
+            FreshRange { start: 1, end: 9 },
+            FreshRange { start: 11, end: 20 },
+        ];
+        assert!(!is_subset(&a, &b_with_gap));
+    }
+
+    #[test]
+    fn test_is_subset_edge_cases() {
+        let ranges = vec![
+            FreshRange { start: 1, end: 5 },
+            FreshRange { start: 3, end: 8 },
+        ];
+        let same_ids = vec![FreshRange { start: 1, end: 8 }];
+
+        // Empty a is a subset of anything
+        assert!(is_subset(&[], &ranges));
+        assert!(is_subset(&[], &[]));
+        assert!(!is_subset(&ranges, &[]));
+
+        // Equal merged sets are mutual subsets
+        assert!(is_subset(&ranges, &same_ids));
+        assert!(is_subset(&same_ids, &ranges));
+    }
+
+    #[test]
+    fn test_range_difference_splits_range() {
+        let a = vec![FreshRange { start: 1, end: 10 }];
+        let b = vec![FreshRange { start: 4, end: 6 }];
+
+        assert_eq!(range_difference(&a, &b), vec![
+            FreshRange { start: 1, end: 3 },
+            FreshRange { start: 7, end: 10 },
+        ]);
+    }
+
+    #[test]
+    fn test_range_difference_multiple_ranges() {
+        let a = vec![
+            FreshRange { start: 20, end: 30 },
+            FreshRange { start: 1, end: 10 },
+        ];
+        let b = vec![
+            FreshRange { start: 0, end: 2 },
+            FreshRange { start: 8, end: 22 },
+            FreshRange { start: 25, end: 25 },
+        ];
+
+        assert_eq!(range_difference(&a, &b), vec![
+            FreshRange { start: 3, end: 7 },
+            FreshRange { start: 23, end: 24 },
+            FreshRange { start: 26, end: 30 },
+        ]);
+    }
+
+    #[test]
+    fn test_range_difference_edge_cases() {
+        let a = vec![FreshRange { start: 5, end: 10 }];
+
+        // Removing nothing keeps everything, removing a superset leaves nothing
+        assert_eq!(range_difference(&a, &[]), a);
+        assert!(range_difference(&a, &[FreshRange { start: 0, end: 100 }]).is_empty());
+        assert!(range_difference(&[], &a).is_empty());
+
+        // Removed range ending at u64::MAX
+        let wide = vec![FreshRange { start: 0, end: u64::MAX }];
+        let top = vec![FreshRange { start: 10, end: u64::MAX }];
+        assert_eq!(range_difference(&wide, &top), vec![FreshRange { start: 0, end: 9 }]);
+    }
+
+    #[test]
+    fn test_fresh_ids_iter_yields_merged_ids() {
+        let ranges = vec![
+            FreshRange { start: 10, end: 12 },
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 11, end: 13 },
+        ];
+
+        let ids: Vec<u64> = fresh_ids_iter(&ranges).collect();
+        assert_eq!(ids, vec![3, 4, 5, 10, 11, 12, 13]);
+        assert_eq!(ids.len(), count_total_fresh_in_ranges(&ranges));
+    }
+
+    #[test]
+    fn test_fresh_ids_iter_is_lazy() {
+        // Taking a few IDs from the full u64 domain must not enumerate it
+        let ranges = vec![FreshRange { start: 0, end: u64::MAX }];
+        let first: Vec<u64> = fresh_ids_iter(&ranges).take(3).collect();
+        assert_eq!(first, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_fresh_ids_iter_bounded() {
+        let ranges = vec![FreshRange { start: 1, end: 10 }];
+
+        assert_eq!(fresh_ids_iter_bounded(&ranges, 10).map(|ids| ids.count()), Some(10));
+        assert!(fresh_ids_iter_bounded(&ranges, 9).is_none());
+        assert!(fresh_ids_iter_bounded(&[FreshRange { start: 0, end: u64::MAX }], u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_nearest_fresh_id() {
+        let ranges = vec![
+            FreshRange { start: 100, end: 200 },
+            FreshRange { start: 300, end: 400 },
+        ];
+
+        // Already fresh
+        assert_eq!(nearest_fresh_id(150, &ranges), Some(150));
+        assert_eq!(nearest_fresh_id(300, &ranges), Some(300));
+
+        // Between ranges, closer to one side
+        assert_eq!(nearest_fresh_id(210, &ranges), Some(200));
+        assert_eq!(nearest_fresh_id(290, &ranges), Some(300));
+
+        // Exactly halfway resolves to the lower ID
+        assert_eq!(nearest_fresh_id(250, &ranges), Some(200));
+
+        // Before the first and after the last range
+        assert_eq!(nearest_fresh_id(5, &ranges), Some(100));
+        assert_eq!(nearest_fresh_id(u64::MAX, &ranges), Some(400));
+    }
+
+    #[test]
+    fn test_nearest_fresh_id_no_ranges() {
+        assert_eq!(nearest_fresh_id(42, &[]), None);
+    }
+
+    #[test]
+    fn test_count_fresh_in_exactly_one() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 100, end: 200 },
+                FreshRange { start: 150, end: 250 },
+            ],
+            available_ingredients: vec![125, 175, 225, 300, 125, 150],
+        };
+
+        // 125 (twice) and 225 are in one range; 175 and 150 are in both; 300 is in neither
+        assert_eq!(count_fresh_in_exactly_one(&data), 3);
+        assert_eq!(count_fresh_ingredients(&data), 5);
+    }
+
+    #[test]
+    fn test_sum_fresh_ingredients() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 100, end: 200 },
+                FreshRange { start: 300, end: 400 },
+            ],
+            available_ingredients: vec![150, 350, 500, 150],
+        };
+
+        // 500 is spoiled; the duplicated 150 is added twice
+        assert_eq!(sum_fresh_ingredients(&data), 650);
+    }
+
+    #[test]
+    fn test_sum_fresh_ingredients_large_ids_do_not_overflow() {
+        let data = InventoryData {
+            fresh_ranges: vec![FreshRange { start: u64::MAX - 1, end: u64::MAX }],
+            available_ingredients: vec![u64::MAX, u64::MAX, u64::MAX - 1],
+        };
+
+        assert_eq!(sum_fresh_ingredients(&data), 3 * u64::MAX as u128 - 1);
+    }
+
+    #[test]
+    fn test_count_spoiled_ingredients() {
+        let data = InventoryData {
+            fresh_ranges: vec![
+                FreshRange { start: 100, end: 200 },
+                FreshRange { start: 300, end: 400 },
+            ],
+            available_ingredients: vec![150, 350, 500, 500, 99, 200],
+        };
+
+        assert_eq!(count_spoiled_ingredients(&data), 3);
+        assert_eq!(
+            count_spoiled_ingredients(&data),
+            data.available_ingredients.len() - count_fresh_ingredients(&data)
+        );
+    }
 }
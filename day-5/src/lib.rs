@@ -0,0 +1,4 @@
+// Library interface for ingredient freshness checker
+
+pub mod parser;
+pub mod checker;
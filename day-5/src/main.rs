@@ -1,6 +1,4 @@
-mod parser;
-mod checker;
-
+use ingredient_freshness_checker::{checker, parser};
 use std::env;
 use std::error::Error;
 use std::fs;
@@ -9,34 +7,48 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Parse command-line arguments
     let args: Vec<String> = env::args().collect();
 
+    // --percent also reports fresh ingredients as a share of those available
+    let show_percent = args.iter().skip(1).any(|arg| arg == "--percent");
+
     // Determine mode: default is "available", can be "total" for total fresh range mode
-    let mode = if args.len() > 1 {
-        args[1].as_str()
-    } else {
-        "available"
-    };
+    let mode = args
+        .iter()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .map_or("available", |arg| arg.as_str());
 
     // Read input.txt file
     let content = fs::read_to_string("input.txt")?;
 
-    // Parse the input to get InventoryData
-    let data = parser::parse_input(&content)?;
-
     // Execute based on mode
     match mode {
         "total" => {
+            // Only the ranges matter here, so accept a file with no ingredients section
+            let fresh_ranges = if content.trim().contains("\n\n") {
+                parser::parse_input(&content)?.fresh_ranges
+            } else {
+                parser::parse_ranges_only(&content)?
+            };
+
             // Total fresh range mode: count all unique IDs across ranges
-            let total_fresh = checker::count_total_fresh_in_ranges(&data.fresh_ranges);
+            let total_fresh = checker::count_total_fresh_in_ranges_u128(&fresh_ranges);
             println!("Total fresh ingredient IDs in ranges: {}", total_fresh);
         }
         "available" => {
+            // Parse the input to get InventoryData
+            let data = parser::parse_input(&content)?;
+
             // Available ingredient mode: count fresh ingredients from available list
             let fresh_count = checker::count_fresh_ingredients(&data);
             println!("Fresh ingredients: {}", fresh_count);
+
+            if show_percent {
+                println!("Fresh percentage: {:.2}%", checker::fresh_percentage(&data));
+            }
         }
         _ => {
             eprintln!("Unknown mode: '{}'. Use 'available' or 'total'.", mode);
-            eprintln!("Usage: {} [available|total]", args[0]);
+            eprintln!("Usage: {} [available|total] [--percent]", args[0]);
             std::process::exit(1);
         }
     }
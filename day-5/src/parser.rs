@@ -1,25 +1,55 @@
 // Parser module for ingredient freshness checker
 // Handles parsing of input file format
 
+use std::collections::BTreeMap;
 use std::fmt;
+use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An inclusive range of fresh ingredient IDs
+///
+/// Ranges order by `start`, then by `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FreshRange {
     pub start: u64,
     pub end: u64,
 }
 
+impl FreshRange {
+    /// Converts the range into a standard inclusive range
+    ///
+    /// # Returns
+    /// * `start..=end`, ready for `contains`, `count` or iteration. An inverted
+    ///   range (start > end) becomes an empty `RangeInclusive`.
+    pub fn as_range(&self) -> RangeInclusive<u64> {
+        self.start..=self.end
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InventoryData {
     pub fresh_ranges: Vec<FreshRange>,
     pub available_ingredients: Vec<u64>,
 }
 
+impl InventoryData {
+    /// Combines two inventory shards into one
+    ///
+    /// # Returns
+    /// * InventoryData with `self`'s ranges followed by `other`'s, and likewise for
+    ///   the available ingredients. Overlapping ranges and duplicate IDs are kept as-is.
+    pub fn merge(mut self, other: InventoryData) -> InventoryData {
+        self.fresh_ranges.extend(other.fresh_ranges);
+        self.available_ingredients.extend(other.available_ingredients);
+        self
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseError {
     InvalidFormat(String),
     InvalidNumber(String),
     MissingSection(String),
+    DuplicateRange(String),
 }
 
 impl fmt::Display for ParseError {
@@ -28,6 +58,7 @@ impl fmt::Display for ParseError {
             ParseError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
             ParseError::InvalidNumber(msg) => write!(f, "Invalid number: {}", msg),
             ParseError::MissingSection(msg) => write!(f, "Missing section: {}", msg),
+            ParseError::DuplicateRange(msg) => write!(f, "Duplicate range: {}", msg),
         }
     }
 }
@@ -66,22 +97,7 @@ pub fn parse_input(content: &str) -> Result<InventoryData, ParseError> {
         ));
     }
 
-    let mut fresh_ranges = Vec::new();
-    for (line_num, line) in ranges_section.lines().enumerate() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue; // Skip empty lines within section
-        }
-
-        match parse_range(line) {
-            Ok(range) => fresh_ranges.push(range),
-            Err(e) => {
-                return Err(ParseError::InvalidFormat(
-                    format!("Line {}: {}", line_num + 1, e)
-                ));
-            }
-        }
-    }
+    let fresh_ranges = parse_ranges_only(ranges_section)?;
 
     // Parse second section: available ingredient IDs
     let ingredients_section = sections[1].trim();
@@ -115,10 +131,79 @@ pub fn parse_input(content: &str) -> Result<InventoryData, ParseError> {
     })
 }
 
-/// Parses a range string in the format "start-end" into a FreshRange
+/// Parses the complete input file, rejecting fresh ranges that appear more than once
+///
+/// # Arguments
+/// * `content` - The full input file content as a string
+///
+/// # Returns
+/// * `Ok(InventoryData)` - The same data `parse_input` would return
+/// * `Err(ParseError)` - Any error from `parse_input`, or `DuplicateRange` naming the
+///   first range that repeats an earlier one exactly
+///
+/// # Format
+/// Same as `parse_input`. Single-value ranges and ranges that merely overlap are
+/// accepted; only identical start and end values count as duplicates.
+pub fn parse_input_strict(content: &str) -> Result<InventoryData, ParseError> {
+    let data = parse_input(content)?;
+
+    let mut seen = BTreeMap::new();
+    for (index, range) in data.fresh_ranges.iter().enumerate() {
+        if let Some(first_index) = seen.insert(range, index) {
+            return Err(ParseError::DuplicateRange(
+                format!("{}-{} appears as range {} and range {}",
+                        range.start, range.end, first_index + 1, index + 1)
+            ));
+        }
+    }
+
+    Ok(data)
+}
+
+/// Parses input consisting only of fresh ranges, with no ingredients section
+///
+/// # Arguments
+/// * `content` - Fresh ranges, one per line in "start-end" format
+///
+/// # Returns
+/// * `Ok(Vec<FreshRange>)` - Successfully parsed ranges
+/// * `Err(ParseError)` - No ranges present or a malformed range line
+///
+/// # Format
+/// Empty lines are skipped, so no blank-line separator is required.
+/// Errors report the 1-based line number within the trimmed content.
+pub fn parse_ranges_only(content: &str) -> Result<Vec<FreshRange>, ParseError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(ParseError::MissingSection(
+            "Fresh ranges section is empty".to_string()
+        ));
+    }
+
+    let mut fresh_ranges = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue; // Skip empty lines within section
+        }
+
+        match parse_range(line) {
+            Ok(range) => fresh_ranges.push(range),
+            Err(e) => {
+                return Err(ParseError::InvalidFormat(
+                    format!("Line {}: {}", line_num + 1, e)
+                ));
+            }
+        }
+    }
+
+    Ok(fresh_ranges)
+}
+
+/// Parses a range string in the format "start-end" or "start..end" into a FreshRange
 ///
 /// # Arguments
-/// * `line` - A string slice containing the range in "start-end" format
+/// * `line` - A string slice containing the range in "start-end" or "start..end" format
 ///
 /// # Returns
 /// * `Ok(FreshRange)` - Successfully parsed range with start <= end
@@ -127,16 +212,21 @@ pub fn parse_input(content: &str) -> Result<InventoryData, ParseError> {
 /// # Examples
 /// * "100-200" -> FreshRange { start: 100, end: 200 }
 /// * "42-42" -> FreshRange { start: 42, end: 42 } (single-value range)
+/// * "100..200" -> FreshRange { start: 100, end: 200 } (Rust-range style)
 pub fn parse_range(line: &str) -> Result<FreshRange, ParseError> {
     let line = line.trim();
 
-    // Split on the dash separator
-    let parts: Vec<&str> = line.split('-').collect();
+    // Split on ".." when present, otherwise on the dash separator
+    let parts: Vec<&str> = if line.contains("..") {
+        line.split("..").collect()
+    } else {
+        line.split('-').collect()
+    };
 
     // Must have exactly 2 parts (start and end)
     if parts.len() != 2 {
         return Err(ParseError::InvalidFormat(
-            format!("Range must be in format 'start-end', got: '{}'", line)
+            format!("Range must be in format 'start-end' or 'start..end', got: '{}'", line)
         ));
     }
 
@@ -166,6 +256,44 @@ pub fn parse_range(line: &str) -> Result<FreshRange, ParseError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fresh_range_ordering() {
+        let mut ranges = vec![
+            FreshRange { start: 10, end: 14 },
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 10, end: 12 },
+            FreshRange { start: 1, end: 20 },
+        ];
+        ranges.sort();
+
+        assert_eq!(ranges, vec![
+            FreshRange { start: 1, end: 20 },
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 10, end: 12 },
+            FreshRange { start: 10, end: 14 },
+        ]);
+    }
+
+    #[test]
+    fn test_inventory_data_merge() {
+        let north = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 10, end: 14 }],
+            available_ingredients: vec![1, 12],
+        };
+        let south = InventoryData {
+            fresh_ranges: vec![FreshRange { start: 3, end: 5 }, FreshRange { start: 12, end: 18 }],
+            available_ingredients: vec![12, 4],
+        };
+
+        let merged = north.merge(south);
+        assert_eq!(merged.fresh_ranges, vec![
+            FreshRange { start: 10, end: 14 },
+            FreshRange { start: 3, end: 5 },
+            FreshRange { start: 12, end: 18 },
+        ]);
+        assert_eq!(merged.available_ingredients, vec![1, 12, 12, 4]);
+    }
+
     #[test]
     fn test_parse_range_valid() {
         let result = parse_range("100-200");
@@ -175,6 +303,20 @@ mod tests {
         assert_eq!(range.end, 200);
     }
 
+    #[test]
+    fn test_parse_range_dot_dot_separator() {
+        assert_eq!(parse_range("100..200").unwrap(), parse_range("100-200").unwrap());
+        assert_eq!(parse_range(" 42 .. 42 ").unwrap(), FreshRange { start: 42, end: 42 });
+    }
+
+    #[test]
+    fn test_parse_range_dot_dot_malformed() {
+        assert!(matches!(parse_range("100.."), Err(ParseError::InvalidNumber(_))));
+        assert!(matches!(parse_range("..200"), Err(ParseError::InvalidNumber(_))));
+        assert!(matches!(parse_range("1..2..3"), Err(ParseError::InvalidFormat(_))));
+        assert!(matches!(parse_range("200..100"), Err(ParseError::InvalidFormat(_))));
+    }
+
     #[test]
     fn test_parse_range_single_value() {
         let result = parse_range("208521390563908-208521390563908");
@@ -349,4 +491,63 @@ mod tests {
         assert_eq!(data.fresh_ranges.len(), 2);
         assert_eq!(data.available_ingredients.len(), 2);
     }
+
+    #[test]
+    fn test_parse_ranges_only_valid() {
+        let result = parse_ranges_only("100-200\n300-400\n");
+        assert_eq!(result.unwrap(), vec![
+            FreshRange { start: 100, end: 200 },
+            FreshRange { start: 300, end: 400 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_ranges_only_empty() {
+        let result = parse_ranges_only("  \n  ");
+        match result {
+            Err(ParseError::MissingSection(_)) => {}
+            _ => panic!("Expected MissingSection error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_ranges_only_malformed_range() {
+        let result = parse_ranges_only("100-200\n300\n");
+        match result {
+            Err(ParseError::InvalidFormat(msg)) => {
+                assert!(msg.starts_with("Line 2"));
+            }
+            _ => panic!("Expected InvalidFormat error"),
+        }
+    }
+
+    #[test]
+    fn test_fresh_range_as_range() {
+        let range = FreshRange { start: 3, end: 5 };
+        assert_eq!(range.as_range(), 3..=5);
+        assert!(range.as_range().contains(&5));
+        assert!(!range.as_range().contains(&6));
+        assert_eq!(range.as_range().count(), 3);
+        assert!(FreshRange { start: 5, end: 3 }.as_range().is_empty());
+    }
+
+    #[test]
+    fn test_parse_input_strict_rejects_duplicate_range() {
+        let input = "100-200\n42-42\n150-250\n100-200\n\n125\n";
+        match parse_input_strict(input) {
+            Err(ParseError::DuplicateRange(msg)) => {
+                assert_eq!(msg, "100-200 appears as range 1 and range 4");
+            }
+            other => panic!("Expected DuplicateRange error, got {:?}", other),
+        }
+
+        // The lenient parser still accepts it
+        assert_eq!(parse_input(input).unwrap().fresh_ranges.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_input_strict_accepts_single_value_and_overlapping_ranges() {
+        let input = "42-42\n100-200\n150-250\n43-43\n\n42\n";
+        assert_eq!(parse_input_strict(input).unwrap(), parse_input(input).unwrap());
+    }
 }
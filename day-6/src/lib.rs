@@ -2,5 +2,7 @@ pub mod parser;
 pub mod solver;
 
 pub use parser::{parse_worksheet, parse_worksheet_horizontal, parse_worksheet_vertical,
-                 format_problem, ParseError, Problem, Operation, ParsingMode};
-pub use solver::{solve_problem, compute_grand_total};
+                 parse_worksheet_with_spans, validate_worksheet,
+                 format_problem, format_problem_vertical, convert_layout, ParseError, Problem, Operation, ParsingMode};
+pub use solver::{solve_problem, solve_problem_checked, solve_all_checked, total_solved, compute_grand_total,
+                 running_totals, problems_with_result, max_result_problem, operation_histogram, SolveError};
@@ -1,16 +1,17 @@
-use math_worksheet_parser::{parse_worksheet, compute_grand_total, solve_problem, ParsingMode};
+use math_worksheet_parser::{parse_worksheet, solve_all_checked, total_solved, ParsingMode, SolveError};
 use std::env;
 use std::fs;
 use std::process;
 
 fn main() {
-    // Check for command-line argument to specify parsing mode
-    let args: Vec<String> = env::args().collect();
-    let mode = if args.len() > 1 && args[1] == "--vertical" {
+    // --vertical selects the parsing mode; --check only validates the worksheet
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mode = if args.iter().any(|arg| arg == "--vertical") {
         ParsingMode::Vertical
     } else {
         ParsingMode::Horizontal
     };
+    let check_only = args.iter().any(|arg| arg == "--check");
 
     // Read input.txt file
     let input = match fs::read_to_string("input.txt") {
@@ -25,33 +26,49 @@ fn main() {
     let problems = match parse_worksheet(&input, mode) {
         Ok(problems) => problems,
         Err(err) => {
-            eprintln!("Error parsing worksheet: {:?}", err);
+            eprintln!("Error parsing worksheet: {}", err);
             process::exit(1);
         }
     };
 
+    // In check mode, stop once the worksheet is known to be well-formed
+    if check_only {
+        println!("Worksheet OK: {} problems found ({:?} mode)", problems.len(), mode);
+        return;
+    }
+
     // Solve all problems and display individual results
     println!("Math Worksheet Parser");
     println!("Mode: {:?}", mode);
     println!("=====================\n");
 
-    for (i, problem) in problems.iter().enumerate() {
-        let result = solve_problem(problem);
-        println!("Problem {}: {} = {}", i + 1, format_problem_inline(problem), result);
+    let results = solve_all_checked(&problems);
+
+    for (i, (problem, result)) in problems.iter().zip(&results).enumerate() {
+        match result {
+            Ok(value) => {
+                println!("Problem {}: {} = {}", i + 1, format_problem_inline(problem), value);
+            }
+            Err(SolveError::Overflow) => {
+                println!("Problem {}: {} = overflow", i + 1, format_problem_inline(problem));
+            }
+            Err(err) => {
+                println!("Problem {}: {} = error ({})", i + 1, format_problem_inline(problem), err);
+            }
+        }
     }
 
-    // Compute and display grand total
-    let grand_total = compute_grand_total(&problems);
+    // Display grand total of the problems that could be solved
     println!("\n=====================");
-    println!("Grand Total: {}", grand_total);
+    match total_solved(&results) {
+        Ok(grand_total) => println!("Grand Total: {}", grand_total),
+        Err(_) => println!("Grand Total: overflow"),
+    }
 }
 
 /// Helper function to format a problem inline for display
 fn format_problem_inline(problem: &math_worksheet_parser::Problem) -> String {
-    let op_symbol = match problem.operation {
-        math_worksheet_parser::Operation::Add => "+",
-        math_worksheet_parser::Operation::Multiply => "*",
-    };
+    let op_symbol = problem.operation.symbol();
 
     problem.numbers
         .iter()
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::ops::Range;
+
 /// Represents the parsing mode for worksheets
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParsingMode {
@@ -8,10 +11,45 @@ pub enum ParsingMode {
 }
 
 /// Represents a mathematical operation
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Operation {
     Add,
     Multiply,
+    /// Left-associative exponentiation: `a ^ b ^ c` is `(a ^ b) ^ c`
+    Exponent,
+    /// Largest of the operands
+    Max,
+    /// Smallest of the operands
+    Min,
+    /// Greatest common divisor of the operands' absolute values
+    Gcd,
+}
+
+impl Operation {
+    /// The symbol that marks this operation in a worksheet
+    pub fn symbol(&self) -> char {
+        match self {
+            Operation::Add => '+',
+            Operation::Multiply => '*',
+            Operation::Exponent => '^',
+            Operation::Max => '>',
+            Operation::Min => '<',
+            Operation::Gcd => 'g',
+        }
+    }
+}
+
+/// Map an operation symbol to its operation, if it is one
+fn operation_from_symbol(ch: char) -> Option<Operation> {
+    match ch {
+        '+' => Some(Operation::Add),
+        '*' => Some(Operation::Multiply),
+        '^' => Some(Operation::Exponent),
+        '>' => Some(Operation::Max),
+        '<' => Some(Operation::Min),
+        'g' => Some(Operation::Gcd),
+        _ => None,
+    }
 }
 
 /// Represents a single math problem
@@ -27,11 +65,64 @@ pub enum ParseError {
     InvalidOperation(char),
     EmptyProblem,
     InvalidNumber(String),
+    /// A parsed number that cannot be written in the requested layout
+    UnformattableNumber(i64),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidOperation(symbol) => write!(f, "invalid operation symbol '{}'", symbol),
+            ParseError::EmptyProblem => write!(f, "problem is missing its operation symbol or numbers"),
+            ParseError::InvalidNumber(number) => write!(f, "invalid number '{}'", number),
+            ParseError::UnformattableNumber(number) => {
+                write!(f, "number {} cannot be written in vertical layout", number)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Column width of a tab stop when expanding tabs
+const TAB_WIDTH: usize = 8;
+
+/// Expand tabs to spaces up to the next tab stop
+///
+/// Lines that mix tabs and spaces then line up the same way they do in an
+/// editor, so tab-separated problems still produce whitespace-only columns.
+fn expand_tabs(line: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(line.len());
+    for ch in line.chars() {
+        if ch == '\t' {
+            let width = TAB_WIDTH - chars.len() % TAB_WIDTH;
+            chars.extend(std::iter::repeat_n(' ', width));
+        } else {
+            chars.push(ch);
+        }
+    }
+    chars
+}
+
+/// Check if a line is a full-line comment (first non-space characters are `//`)
+fn is_comment_line(line: &str) -> bool {
+    line.trim_start().starts_with("//")
 }
 
 /// Transpose input text into columns
+///
+/// Full-line comments are dropped before transposing, so they never add
+/// characters to a column.
 fn transpose_to_columns(input: &str) -> Vec<Vec<char>> {
-    let lines: Vec<&str> = input.lines().collect();
+    // Work in characters rather than bytes so multi-byte characters and tabs
+    // don't shift columns out of line. `lines` already drops the `\r` of a
+    // CRLF ending, but a stray `\r` (such as on a final line with no `\n`)
+    // would otherwise become a column of its own.
+    let lines: Vec<Vec<char>> = input
+        .lines()
+        .filter(|line| !is_comment_line(line))
+        .map(|line| expand_tabs(line.trim_end_matches('\r')))
+        .collect();
     if lines.is_empty() {
         return Vec::new();
     }
@@ -43,12 +134,12 @@ fn transpose_to_columns(input: &str) -> Vec<Vec<char>> {
     let mut columns = vec![Vec::new(); max_len];
 
     for line in lines {
-        for (col_idx, ch) in line.chars().enumerate() {
+        for (col_idx, &ch) in line.iter().enumerate() {
             columns[col_idx].push(ch);
         }
         // Pad shorter lines with spaces
-        for col_idx in line.len()..max_len {
-            columns[col_idx].push(' ');
+        for column in columns.iter_mut().skip(line.len()) {
+            column.push(' ');
         }
     }
 
@@ -61,42 +152,49 @@ fn is_separator_column(column: &[char]) -> bool {
 }
 
 /// Split columns into groups separated by all-whitespace columns
-fn split_into_problem_columns(columns: Vec<Vec<char>>) -> Vec<Vec<Vec<char>>> {
+///
+/// Each group is returned with the range of column indices it occupied.
+fn split_into_problem_columns(columns: Vec<Vec<char>>) -> Vec<(Range<usize>, Vec<Vec<char>>)> {
     let mut problems = Vec::new();
     let mut current_problem = Vec::new();
+    let mut start = 0;
 
-    for column in columns {
+    for (col_idx, column) in columns.into_iter().enumerate() {
         if is_separator_column(&column) {
             if !current_problem.is_empty() {
-                problems.push(current_problem);
+                problems.push((start..col_idx, current_problem));
                 current_problem = Vec::new();
             }
         } else {
+            if current_problem.is_empty() {
+                start = col_idx;
+            }
             current_problem.push(column);
         }
     }
 
     // Don't forget the last problem
     if !current_problem.is_empty() {
-        problems.push(current_problem);
+        let end = start + current_problem.len();
+        problems.push((start..end, current_problem));
     }
 
     problems
 }
 
-/// Extract the operation symbol from the bottom of a problem column group
-fn extract_operation(problem_columns: &[Vec<char>]) -> Result<Operation, ParseError> {
-    // The operation symbol should be at the bottom of the problem
-    // Look through all columns in the problem to find the operation symbol
-    for column in problem_columns {
+/// Find the operation symbol in the bottom row of a sequence of columns
+///
+/// The symbol may sit under any column of the group, so numbers wider than
+/// the symbol don't push it out of place. The first non-blank, non-digit
+/// character found decides the result.
+fn find_operation<'a>(columns: impl Iterator<Item = &'a Vec<char>>) -> Result<Operation, ParseError> {
+    for column in columns {
         if let Some(&last_char) = column.last() {
-            match last_char {
-                '+' => return Ok(Operation::Add),
-                '*' => return Ok(Operation::Multiply),
-                _ if !last_char.is_whitespace() && !last_char.is_ascii_digit() => {
-                    return Err(ParseError::InvalidOperation(last_char));
-                }
-                _ => continue,
+            if let Some(operation) = operation_from_symbol(last_char) {
+                return Ok(operation);
+            }
+            if !last_char.is_whitespace() && !last_char.is_ascii_digit() {
+                return Err(ParseError::InvalidOperation(last_char));
             }
         }
     }
@@ -105,6 +203,12 @@ fn extract_operation(problem_columns: &[Vec<char>]) -> Result<Operation, ParseEr
     Err(ParseError::EmptyProblem)
 }
 
+/// Extract the operation symbol from the bottom of a problem column group
+fn extract_operation(problem_columns: &[Vec<char>]) -> Result<Operation, ParseError> {
+    // Look through all columns left-to-right to find the operation symbol
+    find_operation(problem_columns.iter())
+}
+
 /// Extract numbers from a problem column group
 /// Numbers are read vertically, ignoring the operation symbol at the bottom
 fn extract_numbers(problem_columns: &[Vec<char>]) -> Result<Vec<i64>, ParseError> {
@@ -147,107 +251,106 @@ fn extract_numbers(problem_columns: &[Vec<char>]) -> Result<Vec<i64>, ParseError
 
 /// Parse a worksheet from text format in horizontal mode (original behavior)
 pub fn parse_worksheet_horizontal(input: &str) -> Result<Vec<Problem>, ParseError> {
-    // Handle empty input
-    if input.trim().is_empty() {
-        return Ok(Vec::new());
-    }
+    parse_worksheet(input, ParsingMode::Horizontal)
+}
 
-    // Step 1: Transpose input into columns
-    let columns = transpose_to_columns(input);
+/// Parse a worksheet from text format in vertical mode
+/// In vertical mode, each column represents a single number with digits stacked vertically
+/// (most significant digit at top). Problems are grouped right-to-left.
+pub fn parse_worksheet_vertical(input: &str) -> Result<Vec<Problem>, ParseError> {
+    parse_worksheet(input, ParsingMode::Vertical)
+}
 
-    if columns.is_empty() {
-        return Ok(Vec::new());
+/// Parse a single group of problem columns according to the parsing mode
+fn parse_problem_columns(problem_columns: &[Vec<char>], mode: ParsingMode) -> Result<Problem, ParseError> {
+    let (operation, numbers) = match mode {
+        ParsingMode::Horizontal => {
+            // Numbers are read row by row across the group
+            let operation = extract_operation(problem_columns)?;
+            (operation, extract_numbers(problem_columns)?)
+        }
+        ParsingMode::Vertical => {
+            // Each column is one number, with the operation at the bottom
+            let operation = extract_operation_vertical(problem_columns)?;
+            (operation, extract_numbers_vertical(problem_columns)?)
+        }
+    };
+
+    // Validate that we have at least some numbers
+    if numbers.is_empty() {
+        return Err(ParseError::EmptyProblem);
     }
 
-    // Step 2: Split columns into problem groups
-    let problem_column_groups = split_into_problem_columns(columns);
+    Ok(Problem { numbers, operation })
+}
 
-    // Step 3: Parse each problem group
+/// Parse a worksheet, keeping the column range each problem occupied
+///
+/// Spans are half-open ranges of display columns: characters are counted
+/// after tabs have been expanded to the next multiple of 8, so a span only
+/// matches character indices in the original input when it contains no tabs.
+/// Problems are returned in the same order as `parse_worksheet`, so in
+/// vertical mode the rightmost problem comes first.
+pub fn parse_worksheet_with_spans(input: &str, mode: ParsingMode) -> Result<Vec<(Problem, Range<usize>)>, ParseError> {
     let mut problems = Vec::new();
 
-    for problem_columns in problem_column_groups {
-        if problem_columns.is_empty() {
-            continue;
-        }
-
-        // Extract operation and numbers
-        let operation = extract_operation(&problem_columns)?;
-        let numbers = extract_numbers(&problem_columns)?;
-
-        // Validate that we have at least some numbers
-        if numbers.is_empty() {
-            return Err(ParseError::EmptyProblem);
-        }
-
-        problems.push(Problem { numbers, operation });
+    for (span, problem_columns) in problem_groups(input, mode) {
+        let problem = parse_problem_columns(&problem_columns, mode)?;
+        problems.push((problem, span));
     }
 
     Ok(problems)
 }
 
-/// Parse a worksheet from text format in vertical mode
-/// In vertical mode, each column represents a single number with digits stacked vertically
-/// (most significant digit at top). Problems are grouped right-to-left.
-pub fn parse_worksheet_vertical(input: &str) -> Result<Vec<Problem>, ParseError> {
+/// Split a worksheet into problem column groups in problem order
+///
+/// Each group comes with the span it occupied. In vertical mode the groups are
+/// reversed, since problems are read right-to-left. This ordering defines the
+/// problem indices shared by `parse_worksheet_with_spans` and `validate_worksheet`.
+fn problem_groups(input: &str, mode: ParsingMode) -> Vec<(Range<usize>, Vec<Vec<char>>)> {
     // Handle empty input
     if input.trim().is_empty() {
-        return Ok(Vec::new());
+        return Vec::new();
     }
 
     // Step 1: Transpose input into columns
     let columns = transpose_to_columns(input);
 
     if columns.is_empty() {
-        return Ok(Vec::new());
+        return Vec::new();
     }
 
     // Step 2: Split columns into problem groups (separated by whitespace columns)
-    let problem_column_groups = split_into_problem_columns(columns);
+    let mut problem_column_groups = split_into_problem_columns(columns);
 
-    // Step 3: Parse each problem group
     // In vertical mode, we need to reverse the order since problems are read right-to-left
-    let mut problems = Vec::new();
-
-    for problem_columns in problem_column_groups.into_iter().rev() {
-        if problem_columns.is_empty() {
-            continue;
-        }
-
-        // Extract operation symbol (should be at the bottom of one of the columns)
-        let operation = extract_operation_vertical(&problem_columns)?;
-
-        // Extract numbers - each column is one number
-        let numbers = extract_numbers_vertical(&problem_columns)?;
-
-        // Validate that we have at least some numbers
-        if numbers.is_empty() {
-            return Err(ParseError::EmptyProblem);
-        }
-
-        problems.push(Problem { numbers, operation });
+    if mode == ParsingMode::Vertical {
+        problem_column_groups.reverse();
     }
 
-    Ok(problems)
+    problem_column_groups
+}
+
+/// Check every problem in a worksheet, collecting all parse errors
+///
+/// Unlike `parse_worksheet`, this does not stop at the first failure. Each
+/// error is paired with the index of its problem, using the same ordering as
+/// `parse_worksheet`. An empty result means the whole worksheet parses.
+pub fn validate_worksheet(input: &str, mode: ParsingMode) -> Vec<(usize, ParseError)> {
+    problem_groups(input, mode)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, problem_columns))| {
+            parse_problem_columns(problem_columns, mode).err().map(|err| (index, err))
+        })
+        .collect()
 }
 
 /// Extract the operation symbol in vertical mode
-/// The operation symbol should be at the bottom of the rightmost column in the problem group
+/// The operation symbol may be at the bottom of any column in the problem group
 fn extract_operation_vertical(problem_columns: &[Vec<char>]) -> Result<Operation, ParseError> {
-    // Look for the operation symbol at the bottom of any column
-    for column in problem_columns.iter().rev() {
-        if let Some(&last_char) = column.last() {
-            match last_char {
-                '+' => return Ok(Operation::Add),
-                '*' => return Ok(Operation::Multiply),
-                _ if !last_char.is_whitespace() && !last_char.is_ascii_digit() => {
-                    return Err(ParseError::InvalidOperation(last_char));
-                }
-                _ => continue,
-            }
-        }
-    }
-
-    Err(ParseError::EmptyProblem)
+    // Columns are read right-to-left in vertical mode
+    find_operation(problem_columns.iter().rev())
 }
 
 /// Extract numbers in vertical mode
@@ -265,7 +368,7 @@ fn extract_numbers_vertical(problem_columns: &[Vec<char>]) -> Result<Vec<i64>, P
         for &ch in column {
             if ch.is_ascii_digit() {
                 digit_chars.push(ch);
-            } else if ch == '+' || ch == '*' {
+            } else if operation_from_symbol(ch).is_some() {
                 // Stop when we hit the operation symbol
                 break;
             }
@@ -285,25 +388,24 @@ fn extract_numbers_vertical(problem_columns: &[Vec<char>]) -> Result<Vec<i64>, P
 }
 
 /// Parse a worksheet from text format with specified parsing mode
+///
+/// Lines whose first non-space characters are `//` are treated as comments
+/// and ignored. Inline comments after worksheet data are not supported: they
+/// would be read as part of the columns they sit in.
 pub fn parse_worksheet(input: &str, mode: ParsingMode) -> Result<Vec<Problem>, ParseError> {
-    match mode {
-        ParsingMode::Horizontal => parse_worksheet_horizontal(input),
-        ParsingMode::Vertical => parse_worksheet_vertical(input),
-    }
+    let problems = parse_worksheet_with_spans(input, mode)?;
+    Ok(problems.into_iter().map(|(problem, _)| problem).collect())
 }
 
 /// Format a problem back to columnar text format
-/// Numbers are right-aligned, with the operation symbol at the bottom
+/// Numbers are right-aligned, with the operation symbol at the bottom under
+/// the last column of the widest number
 pub fn format_problem(problem: &Problem) -> String {
     if problem.numbers.is_empty() {
         return String::new();
     }
 
-    // Determine the operation symbol
-    let op_symbol = match problem.operation {
-        Operation::Add => '+',
-        Operation::Multiply => '*',
-    };
+    let op_symbol = problem.operation.symbol();
 
     // Find the maximum width needed (considering all numbers and the operation symbol)
     let max_width = problem.numbers.iter()
@@ -328,6 +430,99 @@ pub fn format_problem(problem: &Problem) -> String {
     lines.join("\n")
 }
 
+/// Format a problem in vertical layout
+/// Each number becomes one column read top to bottom, with the first number in
+/// the rightmost column. Digits are top-aligned and the operation symbol sits at
+/// the bottom of the leftmost column. Negative numbers have no vertical form and
+/// produce `ParseError::UnformattableNumber`.
+pub fn format_problem_vertical(problem: &Problem) -> Result<String, ParseError> {
+    if problem.numbers.is_empty() {
+        return Ok(String::new());
+    }
+
+    if let Some(negative) = problem.numbers.iter().find(|&&number| number < 0) {
+        return Err(ParseError::UnformattableNumber(*negative));
+    }
+
+    // Columns are laid out left-to-right, so the last number comes first
+    let columns: Vec<Vec<char>> = problem.numbers.iter()
+        .rev()
+        .map(|number| number.to_string().chars().collect())
+        .collect();
+    let height = columns.iter().map(|column| column.len()).max().unwrap_or(0);
+
+    let mut lines = Vec::new();
+
+    for row in 0..height {
+        let line: String = columns.iter()
+            .map(|column| column.get(row).copied().unwrap_or(' '))
+            .collect();
+        lines.push(line);
+    }
+
+    lines.push(problem.operation.symbol().to_string());
+
+    Ok(lines.join("\n"))
+}
+
+/// Convert worksheet text from one layout to another
+/// The input is parsed with `from` and each problem is re-formatted with
+/// `format_problem` or `format_problem_vertical`. Problems sit side by side,
+/// separated by a single blank column, with their operation symbols on a shared
+/// bottom row. In vertical output the problems are laid out right-to-left, so
+/// parsing the result with `to` gives back the same problems in the same order.
+/// Converting to vertical can fail after a successful parse, with
+/// `ParseError::UnformattableNumber`, when a problem holds a negative number.
+pub fn convert_layout(input: &str, from: ParsingMode, to: ParsingMode) -> Result<String, ParseError> {
+    let mut problems = parse_worksheet(input, from)?;
+
+    if to == ParsingMode::Vertical {
+        problems.reverse();
+    }
+
+    // Render each problem as its own block of lines, operation row last
+    let mut blocks = Vec::new();
+
+    for problem in &problems {
+        let formatted = match to {
+            ParsingMode::Horizontal => format_problem(problem),
+            ParsingMode::Vertical => format_problem_vertical(problem)?,
+        };
+
+        let block: Vec<String> = if formatted.is_empty() {
+            vec![problem.operation.symbol().to_string()]
+        } else {
+            formatted.lines().map(String::from).collect()
+        };
+        blocks.push(block);
+    }
+
+    let height = blocks.iter().map(|block| block.len()).max().unwrap_or(0);
+    let mut rows = vec![String::new(); height];
+
+    for (index, block) in blocks.iter().enumerate() {
+        let width = block.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+        // Shorter blocks get blank rows above their operation row
+        let (operation_row, number_rows) = block.split_last().expect("blocks are never empty");
+        let blank_rows = height - block.len();
+        let padded = number_rows.iter()
+            .map(String::as_str)
+            .chain(std::iter::repeat_n("", blank_rows))
+            .chain(std::iter::once(operation_row.as_str()));
+
+        for (row, line) in rows.iter_mut().zip(padded) {
+            if index > 0 {
+                row.push(' ');
+            }
+            row.push_str(&format!("{:<width$}", line, width = width));
+        }
+    }
+
+    let rows: Vec<&str> = rows.iter().map(|row| row.trim_end()).collect();
+    Ok(rows.join("\n"))
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -469,3 +664,203 @@ mod tests {
         assert_eq!(problems[0].numbers, vec![234, 123]);
         assert_eq!(problems[0].operation, Operation::Add);
     }
+
+    #[test]
+    fn test_parse_exponent_operation() {
+        let input = "2\n3\n2\n^";
+        let problems = parse_worksheet_horizontal(input).unwrap();
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].numbers, vec![2, 3, 2]);
+        assert_eq!(problems[0].operation, Operation::Exponent);
+        assert_eq!(format_problem(&problems[0]), input);
+    }
+
+    #[test]
+    fn test_parse_max_and_min_operations() {
+        let input = "3 5\n9 1\n> <";
+        let problems = parse_worksheet_horizontal(input).unwrap();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].operation, Operation::Max);
+        assert_eq!(problems[1].operation, Operation::Min);
+        assert_eq!(format_problem(&problems[0]), "3\n9\n>");
+        assert_eq!(format_problem(&problems[1]), "5\n1\n<");
+    }
+
+    #[test]
+    fn test_parse_worksheet_with_spans() {
+        let input = "10  200\n20   30\n+   *  ";
+        let problems = parse_worksheet_with_spans(input, ParsingMode::Horizontal).unwrap();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].0.numbers, vec![10, 20]);
+        assert_eq!(problems[0].1, 0..2);
+        assert_eq!(problems[1].0.numbers, vec![200, 30]);
+        assert_eq!(problems[1].1, 4..7);
+
+        // Vertical mode lists the rightmost problem first
+        let vertical = parse_worksheet_with_spans("1 2\n2 3\n* +", ParsingMode::Vertical).unwrap();
+        assert_eq!(vertical[0].1, 2..3);
+        assert_eq!(vertical[1].1, 0..1);
+
+        // Tabs are expanded before columns are counted
+        let tabbed = parse_worksheet_with_spans("10\t200\n20\t30\n+\t*", ParsingMode::Horizontal).unwrap();
+        assert_eq!(tabbed[0].1, 0..2);
+        assert_eq!(tabbed[1].1, 8..11);
+    }
+
+    #[test]
+    fn test_parse_tab_separated_problems() {
+        let input = "10\t200\n20\t30\n+\t*";
+        let problems = parse_worksheet_horizontal(input).unwrap();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].numbers, vec![10, 20]);
+        assert_eq!(problems[0].operation, Operation::Add);
+        assert_eq!(problems[1].numbers, vec![200, 30]);
+        assert_eq!(problems[1].operation, Operation::Multiply);
+    }
+
+    #[test]
+    fn test_transpose_counts_characters_not_bytes() {
+        let columns = transpose_to_columns("\u{e9}1\n23");
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[1], vec!['1', '3']);
+    }
+
+    #[test]
+    fn test_validate_worksheet_collects_all_errors() {
+        let input = "10 20 30 40\n20 30 40 50\n+  ?  *  !";
+        let errors = validate_worksheet(input, ParsingMode::Horizontal);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, 1);
+        assert!(matches!(errors[0].1, ParseError::InvalidOperation('?')));
+        assert_eq!(errors[1].0, 3);
+        assert!(matches!(errors[1].1, ParseError::InvalidOperation('!')));
+    }
+
+    #[test]
+    fn test_validate_worksheet_valid_input() {
+        let input = "10  20\n20  30\n+   *";
+
+        assert!(validate_worksheet(input, ParsingMode::Horizontal).is_empty());
+        assert!(validate_worksheet("", ParsingMode::Vertical).is_empty());
+    }
+
+    #[test]
+    fn test_operator_under_any_column() {
+        // Operator left-aligned under a wide number
+        let problems = parse_worksheet_horizontal("12345\n    6\n*    ").unwrap();
+        assert_eq!(problems[0].numbers, vec![12345, 6]);
+        assert_eq!(problems[0].operation, Operation::Multiply);
+
+        // Operator under a middle column in vertical mode
+        let problems = parse_worksheet_vertical("123\n456\n + ").unwrap();
+        assert_eq!(problems[0].numbers, vec![36, 25, 14]);
+        assert_eq!(problems[0].operation, Operation::Add);
+    }
+
+    #[test]
+    fn test_format_parse_round_trip_very_different_widths() {
+        let original = Problem {
+            numbers: vec![7, 123456789, 42, 1],
+            operation: Operation::Multiply,
+        };
+
+        let formatted = format_problem(&original);
+        assert!(formatted.ends_with("\n        *"));
+
+        let parsed = parse_worksheet(&formatted, ParsingMode::Horizontal).unwrap();
+        assert_eq!(parsed, vec![original.clone()]);
+        assert_eq!(format_problem(&parsed[0]), formatted);
+    }
+
+    #[test]
+    fn test_parse_crlf_matches_lf() {
+        let lf = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+        let crlf = lf.replace('\n', "\r\n");
+        let trailing_cr = "10  20\r\n20  30\r\n+   *\r";
+
+        for mode in [ParsingMode::Horizontal, ParsingMode::Vertical] {
+            assert_eq!(parse_worksheet(&crlf, mode).unwrap(), parse_worksheet(lf, mode).unwrap());
+        }
+        assert_eq!(
+            parse_worksheet_horizontal(trailing_cr).unwrap(),
+            parse_worksheet_horizontal("10  20\n20  30\n+   *").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_comment_lines_are_ignored() {
+        let plain = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+        let commented = "// first worksheet\n123 328  51 64 \n 45 64  387 23 \n   // a long comment that is wider than every row\n  6 98  215 314\n*   +   *   +  \n";
+
+        for mode in [ParsingMode::Horizontal, ParsingMode::Vertical] {
+            assert_eq!(parse_worksheet(commented, mode).unwrap(), parse_worksheet(plain, mode).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_symbol_round_trips_through_parser() {
+        for operation in [Operation::Add, Operation::Multiply, Operation::Exponent, Operation::Max, Operation::Min, Operation::Gcd] {
+            assert_eq!(operation_from_symbol(operation.symbol()), Some(operation));
+        }
+    }
+
+    #[test]
+    fn test_convert_layout_round_trips_between_modes() {
+        let horizontal = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+        let problems = parse_worksheet(horizontal, ParsingMode::Horizontal).unwrap();
+
+        let vertical = convert_layout(horizontal, ParsingMode::Horizontal, ParsingMode::Vertical).unwrap();
+        assert_eq!(parse_worksheet(&vertical, ParsingMode::Vertical).unwrap(), problems);
+
+        let back = convert_layout(&vertical, ParsingMode::Vertical, ParsingMode::Horizontal).unwrap();
+        assert_eq!(parse_worksheet(&back, ParsingMode::Horizontal).unwrap(), problems);
+        assert_eq!(back, "123 328  51  64\n 45  64 387  23\n  6  98 215 314\n  *   +   *   +");
+    }
+
+    #[test]
+    fn test_convert_layout_pads_problems_of_different_heights() {
+        let horizontal = "1 10\n2 20\n  30\n+  *";
+        let problems = parse_worksheet(horizontal, ParsingMode::Horizontal).unwrap();
+
+        let vertical = convert_layout(horizontal, ParsingMode::Horizontal, ParsingMode::Vertical).unwrap();
+        assert_eq!(vertical, "321 21\n000\n*   +");
+        assert_eq!(parse_worksheet(&vertical, ParsingMode::Vertical).unwrap(), problems);
+    }
+
+    #[test]
+    fn test_format_problem_vertical() {
+        let problem = Problem { numbers: vec![4, 431, 623], operation: Operation::Add };
+        assert_eq!(format_problem_vertical(&problem).unwrap(), "644\n23 \n31 \n+");
+
+        let negative = Problem { numbers: vec![-3], operation: Operation::Add };
+        assert!(matches!(format_problem_vertical(&negative), Err(ParseError::UnformattableNumber(-3))));
+
+        // The horizontal parse succeeds, but the result has no vertical form
+        assert!(parse_worksheet_horizontal("-5\n+").is_ok());
+        assert!(matches!(
+            convert_layout("-5\n+", ParsingMode::Horizontal, ParsingMode::Vertical),
+            Err(ParseError::UnformattableNumber(-5))
+        ));
+    }
+
+    #[test]
+    fn test_parse_gcd_operation() {
+        let input = "12 0\n18 5\ng  g";
+        let problems = parse_worksheet_horizontal(input).unwrap();
+
+        assert_eq!(problems[0], Problem { numbers: vec![12, 18], operation: Operation::Gcd });
+        assert_eq!(problems[1], Problem { numbers: vec![0, 5], operation: Operation::Gcd });
+        assert_eq!(format_problem(&problems[0]), "12\n18\n g");
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        assert_eq!(ParseError::InvalidOperation('?').to_string(), "invalid operation symbol '?'");
+        assert_eq!(ParseError::InvalidNumber("12a".to_string()).to_string(), "invalid number '12a'");
+    }
@@ -1,20 +1,168 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use crate::parser::{Problem, Operation};
 
+/// Errors that can occur while solving a problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The result does not fit in an `i64`
+    Overflow,
+    /// An exponent was negative or too large to apply
+    InvalidExponent(i64),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Overflow => write!(f, "result overflows a 64-bit integer"),
+            SolveError::InvalidExponent(exponent) => write!(f, "invalid exponent: {}", exponent),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
 /// Solve a single problem by applying its operation
+///
+/// # Panics
+///
+/// Panics if the result overflows an `i64`; use `solve_problem_checked`
+/// to handle that case.
 pub fn solve_problem(problem: &Problem) -> i64 {
+    solve_problem_checked(problem).expect("problem result should fit in an i64")
+}
+
+/// Solve a single problem, reporting overflow instead of panicking
+///
+/// Exponentiation folds left-to-right, so `2 ^ 3 ^ 2` is `(2 ^ 3) ^ 2 = 64`.
+/// Max, min, exponent and GCD problems with no numbers evaluate to 0.
+/// GCD ignores signs, and a zero operand leaves the other value unchanged.
+pub fn solve_problem_checked(problem: &Problem) -> Result<i64, SolveError> {
+    let numbers = &problem.numbers;
+
     match problem.operation {
-        Operation::Add => problem.numbers.iter().sum(),
-        Operation::Multiply => problem.numbers.iter().product(),
+        Operation::Add => numbers.iter()
+            .try_fold(0i64, |acc, &n| acc.checked_add(n))
+            .ok_or(SolveError::Overflow),
+        Operation::Multiply => numbers.iter()
+            .try_fold(1i64, |acc, &n| acc.checked_mul(n))
+            .ok_or(SolveError::Overflow),
+        Operation::Exponent => {
+            let Some((&base, exponents)) = numbers.split_first() else {
+                return Ok(0);
+            };
+            exponents.iter().try_fold(base, |acc, &exponent| {
+                let exp = u32::try_from(exponent).map_err(|_| SolveError::InvalidExponent(exponent))?;
+                acc.checked_pow(exp).ok_or(SolveError::Overflow)
+            })
+        }
+        Operation::Max => Ok(numbers.iter().copied().max().unwrap_or(0)),
+        Operation::Min => Ok(numbers.iter().copied().min().unwrap_or(0)),
+        Operation::Gcd => {
+            let result = numbers.iter().fold(0u64, |acc, &n| gcd(acc, n.unsigned_abs()));
+            // Only a GCD of 2^63 (from i64::MIN operands) does not fit back in an i64
+            i64::try_from(result).map_err(|_| SolveError::Overflow)
+        }
+    }
+}
+
+/// Greatest common divisor using the Euclidean algorithm, with gcd(a, 0) = a
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
     }
+    a
+}
+
+/// Solve every problem, reporting each failure independently
+///
+/// Results are returned in problem order, so one overflowing problem does not
+/// prevent the rest of the worksheet from being solved.
+pub fn solve_all_checked(problems: &[Problem]) -> Vec<Result<i64, SolveError>> {
+    problems.iter()
+        .map(solve_problem_checked)
+        .collect()
+}
+
+/// Total the results of the problems that could be solved
+///
+/// Problems that failed are skipped, so one bad problem does not lose the
+/// rest of the worksheet. Returns `SolveError::Overflow` if the sum of the
+/// remaining results does not fit in an `i64`.
+pub fn total_solved(results: &[Result<i64, SolveError>]) -> Result<i64, SolveError> {
+    results.iter()
+        .filter_map(|result| result.as_ref().ok())
+        .try_fold(0i64, |total, &value| total.checked_add(value))
+        .ok_or(SolveError::Overflow)
 }
 
 /// Compute the grand total by summing all problem results
 pub fn compute_grand_total(problems: &[Problem]) -> i64 {
     problems.iter()
-        .map(|problem| solve_problem(problem))
+        .map(solve_problem)
         .sum()
 }
 
+/// Compute the cumulative total after each problem
+///
+/// Element `i` is the sum of the first `i + 1` problem results, so the last
+/// element equals `compute_grand_total`. Returns an empty vector for no problems.
+/// Fails with the first error met, whether a problem cannot be solved or a
+/// running total overflows an `i64`.
+pub fn running_totals(problems: &[Problem]) -> Result<Vec<i64>, SolveError> {
+    let mut total = 0i64;
+    let mut totals = Vec::with_capacity(problems.len());
+
+    for problem in problems {
+        total = total
+            .checked_add(solve_problem_checked(problem)?)
+            .ok_or(SolveError::Overflow)?;
+        totals.push(total);
+    }
+
+    Ok(totals)
+}
+
+/// Find every problem whose result equals `target`
+///
+/// Returns the matching problem indices in ascending order, or an empty vector
+/// if no problem produces `target`. Problems that cannot be solved never match.
+pub fn problems_with_result(problems: &[Problem], target: i64) -> Vec<usize> {
+    problems.iter()
+        .enumerate()
+        .filter(|(_, problem)| solve_problem_checked(problem) == Ok(target))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Find the problem with the largest result
+///
+/// Returns the index and result of that problem, or `None` if no problem can
+/// be solved. Problems that fail are skipped, and ties resolve to the earliest
+/// index.
+pub fn max_result_problem(problems: &[Problem]) -> Option<(usize, i64)> {
+    problems.iter()
+        .map(solve_problem_checked)
+        .enumerate()
+        .filter_map(|(index, result)| result.ok().map(|value| (index, value)))
+        .fold(None, |best, (index, result)| match best {
+            Some((_, best_result)) if best_result >= result => best,
+            _ => Some((index, result)),
+        })
+}
+
+/// Count how many problems use each operation
+///
+/// Operations that do not appear in `problems` are absent from the map.
+pub fn operation_histogram(problems: &[Problem]) -> HashMap<Operation, usize> {
+    let mut histogram = HashMap::new();
+    for problem in problems {
+        *histogram.entry(problem.operation.clone()).or_insert(0) += 1;
+    }
+    histogram
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +239,249 @@ mod tests {
         ];
         assert_eq!(compute_grand_total(&problems), 30);
     }
+
+    #[test]
+    fn test_solve_exponent_is_left_associative() {
+        let problem = Problem {
+            numbers: vec![2, 3, 2],
+            operation: Operation::Exponent,
+        };
+        // (2^3)^2 = 64, not 2^(3^2) = 512
+        assert_eq!(solve_problem(&problem), 64);
+    }
+
+    #[test]
+    fn test_solve_exponent_overflow_is_an_error() {
+        let problem = Problem {
+            numbers: vec![10, 19],
+            operation: Operation::Exponent,
+        };
+        assert_eq!(solve_problem_checked(&problem), Err(SolveError::Overflow));
+    }
+
+    #[test]
+    fn test_solve_exponent_negative_exponent_is_an_error() {
+        let problem = Problem {
+            numbers: vec![2, -1],
+            operation: Operation::Exponent,
+        };
+        assert_eq!(solve_problem_checked(&problem), Err(SolveError::InvalidExponent(-1)));
+    }
+
+    #[test]
+    fn test_solve_max_and_min() {
+        let max = Problem {
+            numbers: vec![7, 42, -3],
+            operation: Operation::Max,
+        };
+        let min = Problem {
+            numbers: vec![7, 42, -3],
+            operation: Operation::Min,
+        };
+        assert_eq!(solve_problem(&max), 42);
+        assert_eq!(solve_problem(&min), -3);
+    }
+
+    #[test]
+    fn test_solve_gcd() {
+        let gcd_of = |numbers: Vec<i64>| solve_problem_checked(&Problem {
+            numbers,
+            operation: Operation::Gcd,
+        });
+
+        assert_eq!(gcd_of(vec![12, 18]), Ok(6));
+        assert_eq!(gcd_of(vec![0, 5]), Ok(5));
+        assert_eq!(gcd_of(vec![-12, 18, 27]), Ok(3));
+        assert_eq!(gcd_of(vec![7]), Ok(7));
+        assert_eq!(gcd_of(vec![-7]), Ok(7));
+        assert_eq!(gcd_of(vec![]), Ok(0));
+        assert_eq!(gcd_of(vec![i64::MIN, 0]), Err(SolveError::Overflow));
+    }
+
+    #[test]
+    fn test_operation_histogram() {
+        let problems = vec![
+            Problem {
+                numbers: vec![10, 20],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![2, 3],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![100, 50],
+                operation: Operation::Add,
+            },
+        ];
+        let histogram = operation_histogram(&problems);
+
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&Operation::Add], 2);
+        assert_eq!(histogram[&Operation::Multiply], 1);
+        assert!(operation_histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_max_result_problem() {
+        let problems = vec![
+            Problem {
+                numbers: vec![10, 20],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![20, 30],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![100, 50],
+                operation: Operation::Add,
+            },
+        ];
+        assert_eq!(max_result_problem(&problems), Some((1, 600)));
+        assert_eq!(max_result_problem(&[]), None);
+    }
+
+    #[test]
+    fn test_max_result_problem_ties_resolve_to_earliest() {
+        let problems = vec![
+            Problem {
+                numbers: vec![1, 2],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![2, 3],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![3, 3],
+                operation: Operation::Add,
+            },
+        ];
+        assert_eq!(max_result_problem(&problems), Some((1, 6)));
+    }
+
+    #[test]
+    fn test_solve_all_checked_reports_overflow_per_problem() {
+        let problems = vec![
+            Problem {
+                numbers: vec![10, 20],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![i64::MAX, 2],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![2, 3],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![5, 9, 1],
+                operation: Operation::Max,
+            },
+        ];
+        let results = solve_all_checked(&problems);
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[1], Err(SolveError::Overflow));
+
+        assert_eq!(total_solved(&results), Ok(30 + 6 + 9));
+    }
+
+    #[test]
+    fn test_total_solved_reports_overflow_of_the_sum() {
+        let problems = vec![
+            Problem {
+                numbers: vec![i64::MAX - 10, 5],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![i64::MAX, 2],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![20],
+                operation: Operation::Add,
+            },
+        ];
+        let results = solve_all_checked(&problems);
+
+        // Each solvable result fits on its own, but together they exceed i64::MAX
+        assert!(results[0].is_ok() && results[2].is_ok());
+        assert_eq!(total_solved(&results), Err(SolveError::Overflow));
+        assert_eq!(total_solved(&results[..1]), Ok(i64::MAX - 5));
+        assert_eq!(total_solved(&[]), Ok(0));
+    }
+
+    #[test]
+    fn test_running_totals() {
+        let problems = vec![
+            Problem {
+                numbers: vec![10, 20],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![2, 3],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![100, 50],
+                operation: Operation::Add,
+            },
+        ];
+        let totals = running_totals(&problems).unwrap();
+
+        assert_eq!(totals, vec![30, 36, 186]);
+        assert_eq!(totals.last(), Some(&compute_grand_total(&problems)));
+        assert_eq!(running_totals(&[]), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_overflowing_problems_do_not_panic() {
+        let problems = vec![
+            Problem {
+                numbers: vec![i64::MAX - 1, 1],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![i64::MAX, 2],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![5],
+                operation: Operation::Add,
+            },
+        ];
+
+        // An unsolvable problem and an overflowing running total are both errors
+        assert_eq!(running_totals(&problems), Err(SolveError::Overflow));
+        assert_eq!(running_totals(&[problems[0].clone(), problems[2].clone()]), Err(SolveError::Overflow));
+
+        assert_eq!(max_result_problem(&problems), Some((0, i64::MAX)));
+        assert_eq!(max_result_problem(&problems[1..2]), None);
+        assert_eq!(problems_with_result(&problems, 5), vec![2]);
+    }
+
+    #[test]
+    fn test_problems_with_result() {
+        let problems = vec![
+            Problem {
+                numbers: vec![10, 20],
+                operation: Operation::Add,
+            },
+            Problem {
+                numbers: vec![20, 30],
+                operation: Operation::Multiply,
+            },
+            Problem {
+                numbers: vec![600],
+                operation: Operation::Add,
+            },
+        ];
+
+        assert_eq!(problems_with_result(&problems, 600), vec![1, 2]);
+        assert_eq!(problems_with_result(&problems, 30), vec![0]);
+        assert!(problems_with_result(&problems, 7).is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use math_worksheet_parser::{parse_worksheet, compute_grand_total, ParsingMode};
+use math_worksheet_parser::{parse_worksheet, compute_grand_total, operation_histogram, problems_with_result, Operation, ParsingMode};
 
 #[test]
 fn test_example_worksheet_horizontal() {
@@ -52,6 +52,27 @@ fn test_simple_worksheet() {
     assert_eq!(total, 630);
 }
 
+#[test]
+fn test_simple_worksheet_problems_with_result() {
+    let input = "10  20\n20  30\n+   *";
+
+    let problems = parse_worksheet(input, ParsingMode::Horizontal).expect("Failed to parse");
+
+    assert_eq!(problems_with_result(&problems, 600), vec![1]);
+    assert_eq!(problems_with_result(&problems, 30), vec![0]);
+}
+
+#[test]
+fn test_simple_worksheet_operation_histogram() {
+    let input = "10  20\n20  30\n+   *";
+
+    let problems = parse_worksheet(input, ParsingMode::Horizontal).expect("Failed to parse");
+
+    let histogram = operation_histogram(&problems);
+    assert_eq!(histogram.get(&Operation::Add), Some(&1));
+    assert_eq!(histogram.get(&Operation::Multiply), Some(&1));
+}
+
 #[test]
 fn test_simple_vertical() {
     // Example from the problem description